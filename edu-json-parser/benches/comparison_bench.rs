@@ -1,7 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-use edu_json_parser::{parse_json};
+use edu_json_parser::{parse_json, JsonParser, validate_json, parse_json_simd, parse_json_parallel, parse_json_interned, parse_json_with_interner, KeyInterner, Node};
 use serde_json::Value;
+use std::collections::HashSet;
 
 const MANY_CARDS: &'static str = include!("many.json");
 
@@ -48,6 +49,20 @@ fn bench_edu_just_nodes_twitter() -> String {
     String::from("42")
 }
 
+fn bench_edu_simd_twitter() -> String {
+    let _ = parse_json_simd(TWITTER_JSON);
+    let _ = parse_json_simd(CANADA_JSON);
+    let _ = parse_json_simd(CITM_CATALOG_JSON);
+    String::from("42")
+}
+
+fn bench_edu_validate_twitter() -> String {
+    let _ = validate_json(TWITTER_JSON.as_bytes());
+    let _ = validate_json(CANADA_JSON.as_bytes());
+    let _ = validate_json(CITM_CATALOG_JSON.as_bytes());
+    String::from("42")
+}
+
 fn json_bench_twitter() -> String {
     let _ = json::parse(TWITTER_JSON);
     let _ = json::parse(CANADA_JSON);
@@ -75,7 +90,56 @@ fn just_iterate_twitter() -> String {
     String::from("42")
 }
 
+fn bench_edu_interned_twitter() -> String {
+    let _ = parse_json_interned(TWITTER_JSON);
+    let _ = parse_json_interned(CANADA_JSON);
+    let _ = parse_json_interned(CITM_CATALOG_JSON);
+    String::from("42")
+}
+
+// Heap bytes taken by key text; keys sharing one allocation are counted once
+fn key_heap_bytes(node: &Node, seen: &mut HashSet<*const u8>) -> usize {
+    match node {
+        Node::Array(arr) => arr.iter().map(|item| key_heap_bytes(item, seen)).sum(),
+        Node::Object(dict) => dict.iter().map(|(k, v)| {
+            let own = if k.is_heap_allocated() && seen.insert(k.as_str().as_ptr()) {
+                k.len()
+            } else {
+                0
+            };
+            own + key_heap_bytes(v, seen)
+        }).sum(),
+        _ => 0
+    }
+}
+
+fn report_key_memory() {
+    let documents = [
+        ("twitter", TWITTER_JSON),
+        ("canada", CANADA_JSON),
+        ("citm_catalog", CITM_CATALOG_JSON),
+        ("many cards", MANY_CARDS)
+    ];
+    let mut shared = KeyInterner::new();
+    let (mut plain_total, mut shared_total) = (0, 0);
+    for (name, content) in documents.iter() {
+        let plain = key_heap_bytes(&parse_json(content).unwrap(), &mut HashSet::new());
+        let interned = key_heap_bytes(&parse_json_interned(content).unwrap(), &mut HashSet::new());
+        parse_json_with_interner(content, &mut shared).unwrap();
+        println!("key heap bytes for {}: {} plain, {} interned", name, plain, interned);
+        plain_total += plain;
+        shared_total += interned;
+    }
+    println!(
+        "key heap bytes for all documents: {} plain, {} interned per document, {} with one shared interner",
+        plain_total,
+        shared_total,
+        shared.heap_bytes()
+    );
+}
+
 fn bench_json(c: &mut Criterion) {
+    report_key_memory();
     c.bench_function("bench edu-json-parser", |b| b.iter(|| {
         let x = bench_edu_just_nodes();
         black_box(x)
@@ -96,6 +160,18 @@ fn bench_json(c: &mut Criterion) {
         let x = bench_edu_just_nodes_twitter();
         black_box(x)
     }));
+    c.bench_function("bench edu-json-parser two stage twitter, canada, citm_catalog", |b| b.iter(|| {
+        let x = bench_edu_simd_twitter();
+        black_box(x)
+    }));
+    c.bench_function("bench edu-json-parser validate twitter, canada, citm_catalog", |b| b.iter(|| {
+        let x = bench_edu_validate_twitter();
+        black_box(x)
+    }));
+    c.bench_function("bench edu-json-parser interned twitter, canada, citm_catalog", |b| b.iter(|| {
+        let x = bench_edu_interned_twitter();
+        black_box(x)
+    }));
     c.bench_function("bench json library twitter, canada, citm_catalog", |b| b.iter(|| {
         let x = json_bench_twitter();
         black_box(x)
@@ -110,5 +186,60 @@ fn bench_json(c: &mut Criterion) {
     }));
 }
 
-criterion_group!(json, bench_json);
+// About 17 MB of cards, built from the many cards sample
+fn generate_large_array() -> String {
+    let cards = MANY_CARDS.trim();
+    let inner = &cards[1..cards.len() - 1];
+    let mut large = String::from("[");
+    for i in 0..300 {
+        if i > 0 {
+            large.push(',');
+        }
+        large.push_str(inner);
+    }
+    large.push(']');
+    large
+}
+
+fn bench_large(c: &mut Criterion) {
+    let large = generate_large_array();
+    let mut group = c.benchmark_group("large array");
+    group.sample_size(10);
+    group.bench_function("bench edu-json-parser large array", |b| b.iter(|| {
+        black_box(parse_json(&large).is_ok())
+    }));
+    group.bench_function("bench edu-json-parser parallel large array", |b| b.iter(|| {
+        black_box(parse_json_parallel(&large).is_ok())
+    }));
+    group.bench_function("bench serde library large array", |b| b.iter(|| {
+        let parsed: serde_json::Result<Value> = serde_json::from_str(&large);
+        black_box(parsed.is_ok())
+    }));
+    group.finish();
+}
+
+// Every card of the many cards sample as a message of its own
+fn card_messages() -> Vec<String> {
+    match parse_json(MANY_CARDS).unwrap() {
+        Node::Array(cards) => cards.iter().map(|card| card.to_json()).collect(),
+        _ => Vec::new()
+    }
+}
+
+fn bench_messages(c: &mut Criterion) {
+    let messages = card_messages();
+    let mut parser = JsonParser::new();
+    c.bench_function("bench edu-json-parser small messages", |b| b.iter(|| {
+        for message in messages.iter() {
+            black_box(parse_json(message).is_ok());
+        }
+    }));
+    c.bench_function("bench edu-json-parser reusable parser small messages", |b| b.iter(|| {
+        for message in messages.iter() {
+            black_box(parser.parse(message).is_ok());
+        }
+    }));
+}
+
+criterion_group!(json, bench_json, bench_large, bench_messages);
 criterion_main!(json);
\ No newline at end of file
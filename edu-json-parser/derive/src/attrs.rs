@@ -0,0 +1,105 @@
+use syn::{Attribute, ExprPath, LitStr, Result};
+
+pub enum DefaultValue {
+    Trait,
+    Function(ExprPath)
+}
+
+#[derive(Default)]
+pub struct FieldAttrs {
+    pub rename: Option<String>,
+    pub default: Option<DefaultValue>,
+    pub skip: bool,
+    pub flatten: bool
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut result = FieldAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("parsable")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    result.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("default") {
+                    result.default = Some(if meta.input.peek(syn::Token![=]) {
+                        DefaultValue::Function(meta.value()?.parse::<LitStr>()?.parse()?)
+                    } else {
+                        DefaultValue::Trait
+                    });
+                } else if meta.path.is_ident("skip") {
+                    result.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    result.flatten = true;
+                } else {
+                    return Err(meta.error("expected `rename`, `default`, `skip` or `flatten`"));
+                }
+                Ok(())
+            })?;
+            if result.flatten && (result.rename.is_some() || result.default.is_some()) {
+                return Err(syn::Error::new_spanned(attr, "`flatten` goes without `rename` and `default`"));
+            }
+        }
+        Ok(result)
+    }
+}
+
+// How an enum is told apart in JSON, externally tagged (`{"Variant": value}`) if nothing is set
+#[derive(Default)]
+pub struct ContainerAttrs {
+    pub tag: Option<String>,
+    pub content: Option<String>,
+    pub untagged: bool
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut result = ContainerAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("parsable")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    result.tag = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("content") {
+                    result.content = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("untagged") {
+                    result.untagged = true;
+                } else {
+                    return Err(meta.error("expected `tag`, `content` or `untagged`"));
+                }
+                Ok(())
+            })?;
+            if result.content.is_some() && result.tag.is_none() {
+                return Err(syn::Error::new_spanned(attr, "`content` goes with `tag`"));
+            }
+            if result.untagged && result.tag.is_some() {
+                return Err(syn::Error::new_spanned(attr, "`untagged` goes without `tag`"));
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tag.is_none() && self.content.is_none() && !self.untagged
+    }
+}
+
+#[derive(Default)]
+pub struct VariantAttrs {
+    pub rename: Option<String>
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut result = VariantAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("parsable")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    result.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename`"))
+                }
+            })?;
+        }
+        Ok(result)
+    }
+}
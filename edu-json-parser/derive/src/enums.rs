@@ -0,0 +1,215 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DataEnum, Fields, Ident, Result, Type, Variant};
+use crate::attrs::{ContainerAttrs, VariantAttrs};
+use crate::structs;
+
+struct Case<'a> {
+    name: String,
+    variant: &'a Variant
+}
+
+impl<'a> Case<'a> {
+    fn is_unit(&self) -> bool {
+        matches!(self.variant.fields, Fields::Unit)
+    }
+
+    // A closure which reads the variant out of a node, for errors to be caught before
+    // they leave `parse_node`
+    fn parser(&self, enum_name: &Ident) -> Result<TokenStream> {
+        let ident = &self.variant.ident;
+        let body = structs::expand(&quote!(#enum_name::#ident), &self.variant.fields)?;
+        Ok(quote! {
+            (|json: &::edu_json_parser::Node| -> ::std::result::Result<Self, ::edu_json_parser::DecodeError> {
+                #body
+            })
+        })
+    }
+}
+
+// Externally tagged enums are `"Unit"` or `{"Variant": value}`, internally tagged ones are
+// `{"tag": "Variant", ...fields}`, adjacently tagged ones are `{"tag": "Variant", "content": value}`
+// and untagged ones are whatever the first variant which could be read out of the value
+pub fn expand(enum_name: &Ident, data: &DataEnum, attrs: &ContainerAttrs) -> Result<TokenStream> {
+    if data.variants.is_empty() {
+        return Err(syn::Error::new_spanned(enum_name, "`Parsable` can't be derived for an enum without variants"));
+    }
+    let cases = data.variants.iter()
+        .map(|variant| {
+            let attrs = VariantAttrs::parse(&variant.attrs)?;
+            let name = attrs.rename.unwrap_or_else(|| variant.ident.to_string());
+            Ok(Case { name, variant })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    match (&attrs.tag, &attrs.content) {
+        _ if attrs.untagged => untagged(enum_name, &cases),
+        (Some(tag), None) => internally_tagged(enum_name, &cases, tag),
+        (Some(tag), Some(content)) => adjacently_tagged(enum_name, &cases, tag, content),
+        _ => externally_tagged(enum_name, &cases)
+    }
+}
+
+fn externally_tagged(enum_name: &Ident, cases: &[Case]) -> Result<TokenStream> {
+    let (units, others): (Vec<&Case>, Vec<&Case>) = cases.iter().partition(|case| case.is_unit());
+    let unit_names = units.iter().map(|case| &case.name).collect::<Vec<_>>();
+    let unit_idents = units.iter().map(|case| &case.variant.ident);
+    let other_names = others.iter().map(|case| &case.name).collect::<Vec<_>>();
+    let other_parsers = others.iter()
+        .map(|case| case.parser(enum_name))
+        .collect::<Result<Vec<_>>>()?;
+    let string_arm = if units.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            ::edu_json_parser::Node::String(name) => match name.as_str() {
+                #(#unit_names => ::std::result::Result::Ok(#enum_name::#unit_idents),)*
+                other => ::std::result::Result::Err(
+                    ::edu_json_parser::DecodeError::unknown_variant(other, &[#(#unit_names),*])
+                )
+            },
+        }
+    };
+    let object_arm = if others.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            ::edu_json_parser::Node::Object(fields) => {
+                let mut entries = fields.iter();
+                match (entries.next(), entries.next()) {
+                    (::std::option::Option::Some((name, json)), ::std::option::Option::None) => match name.as_str() {
+                        #(#other_names => #other_parsers(json).map_err(|e| e.in_field(#other_names)),)*
+                        other => ::std::result::Result::Err(
+                            ::edu_json_parser::DecodeError::unknown_variant(other, &[#(#other_names),*])
+                        )
+                    },
+                    _ => ::std::result::Result::Err(::edu_json_parser::DecodeError::new(
+                        format!("expected an object with a single key, found {} keys", fields.len())
+                    ))
+                }
+            },
+        }
+    };
+    let expected = match (units.is_empty(), others.is_empty()) {
+        (false, false) => "string or object",
+        (false, true) => "string",
+        _ => "object"
+    };
+    Ok(quote! {
+        match json {
+            #string_arm
+            #object_arm
+            _ => ::std::result::Result::Err(::edu_json_parser::DecodeError::wrong_type(#expected, json.kind()))
+        }
+    })
+}
+
+fn internally_tagged(enum_name: &Ident, cases: &[Case], tag: &str) -> Result<TokenStream> {
+    let arms = cases.iter()
+        .map(|case| {
+            let name = &case.name;
+            let ident = &case.variant.ident;
+            match &case.variant.fields {
+                Fields::Unit => Ok(quote!(#name => ::std::result::Result::Ok(#enum_name::#ident))),
+                Fields::Unnamed(unnamed) if unnamed.unnamed.len() != 1 => Err(syn::Error::new_spanned(
+                    case.variant, "tuple variants can't be internally tagged, as they are not objects"
+                )),
+                Fields::Unnamed(unnamed) if is_never_an_object(&unnamed.unnamed[0].ty) => Err(syn::Error::new_spanned(
+                    &unnamed.unnamed[0].ty, "a newtype variant can be internally tagged only if it holds a struct, \
+                    which is read out of the fields next to the tag"
+                )),
+                _ => {
+                    let parser = case.parser(enum_name)?;
+                    Ok(quote!(#name => #parser(json)))
+                }
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(tagged(cases, tag, arms))
+}
+
+// A derive can't see whether a type is a struct, so only types which are never read out of
+// an object are caught here. Anything else gets the whole object, tag included, when decoding
+fn is_never_an_object(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.qself.is_none() && path.path.segments.last().is_some_and(|segment| matches!(
+            segment.ident.to_string().as_str(),
+            "bool" | "char" | "str" | "String" | "SmolStr" | "f32" | "f64" |
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
+            "Vec" | "VecDeque" | "HashSet" | "BTreeSet"
+        )),
+        Type::Paren(inner) => is_never_an_object(&inner.elem),
+        Type::Group(inner) => is_never_an_object(&inner.elem),
+        Type::Array(_) | Type::Slice(_) | Type::Tuple(_) => true,
+        _ => false
+    }
+}
+
+fn adjacently_tagged(enum_name: &Ident, cases: &[Case], tag: &str, content: &str) -> Result<TokenStream> {
+    let arms = cases.iter()
+        .map(|case| {
+            let name = &case.name;
+            let ident = &case.variant.ident;
+            if case.is_unit() {
+                return Ok(quote!(#name => ::std::result::Result::Ok(#enum_name::#ident)));
+            }
+            let parser = case.parser(enum_name)?;
+            Ok(quote! {
+                #name => #parser(json.get(#content)?).map_err(|e| e.in_field(#content))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(tagged(cases, tag, arms))
+}
+
+// Both kinds of tagged enums are objects with the name of a variant in the `tag` field
+fn tagged(cases: &[Case], tag: &str, arms: Vec<TokenStream>) -> TokenStream {
+    let names = cases.iter().map(|case| &case.name);
+    quote! {
+        if !json.is_object() {
+            return ::std::result::Result::Err(
+                ::edu_json_parser::DecodeError::wrong_type("object", json.kind())
+            );
+        }
+        match json.get_string(#tag)? {
+            #(#arms,)*
+            other => ::std::result::Result::Err(
+                ::edu_json_parser::DecodeError::unknown_variant(other, &[#(#names),*]).in_field(#tag)
+            )
+        }
+    }
+}
+
+fn untagged(enum_name: &Ident, cases: &[Case]) -> Result<TokenStream> {
+    let enum_name_str = enum_name.to_string();
+    let attempts = cases.iter()
+        .map(|case| {
+            let name = &case.name;
+            let ident = &case.variant.ident;
+            let attempt = if case.is_unit() {
+                let message = format!("expected \"{}\"", name);
+                quote! {
+                    match json {
+                        ::edu_json_parser::Node::String(s) if s.as_str() == #name => {
+                            ::std::result::Result::Ok(#enum_name::#ident)
+                        },
+                        _ => ::std::result::Result::Err(::edu_json_parser::DecodeError::new(#message))
+                    }
+                }
+            } else {
+                let parser = case.parser(enum_name)?;
+                quote!(#parser(json))
+            };
+            Ok(quote! {
+                match #attempt {
+                    ::std::result::Result::Ok(value) => return ::std::result::Result::Ok(value),
+                    ::std::result::Result::Err(e) => tried.push((#name, e))
+                }
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(quote! {
+        let mut tried = ::std::vec::Vec::new();
+        #(#attempts)*
+        ::std::result::Result::Err(::edu_json_parser::DecodeError::no_matching_variant(#enum_name_str, tried))
+    })
+}
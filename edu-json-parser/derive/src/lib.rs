@@ -0,0 +1,65 @@
+extern crate proc_macro;
+
+mod attrs;
+mod enums;
+mod structs;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Result};
+use crate::attrs::ContainerAttrs;
+
+// `#[derive(Parsable)]`, which reads a value the way a hand written `Parsable::parse_node`
+// would, with errors pointing at the failed value. Field attributes go to `#[parsable(...)]`:
+//   rename = "name"   the key in JSON, the field name by default
+//   default           a missing field or null is `Default::default()`
+//   default = "path"  the same with a function to call
+//   skip              never read, always `Default::default()`
+//   flatten           the value is read from the fields of this very object
+// Enums are externally tagged, as `"Unit"` or `{"Variant": value}`, unless they have one of
+//   tag = "type"                     `{"type": "Variant", ...fields}`, where a newtype variant
+//                                    has to hold a struct, which gets the whole object
+//   tag = "t", content = "c"         `{"t": "Variant", "c": value}`
+//   untagged                         the first variant which could be read
+// and variants could have `rename = "name"` on their own
+#[proc_macro_derive(Parsable, attributes(parsable))]
+pub fn derive_parsable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(body) => implement(&input, body).into(),
+        Err(error) => error.to_compile_error().into()
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let name = &input.ident;
+    match &input.data {
+        Data::Struct(_) if !attrs.is_empty() => {
+            Err(syn::Error::new_spanned(name, "`tag`, `content` and `untagged` are only for enums"))
+        },
+        Data::Struct(data) => structs::expand(&quote!(#name), &data.fields),
+        Data::Enum(data) => enums::expand(name, data, &attrs),
+        Data::Union(_) => Err(syn::Error::new_spanned(name, "`Parsable` can't be derived for unions"))
+    }
+}
+
+// Every type parameter has to be `Parsable` itself
+fn implement(input: &DeriveInput, body: TokenStream2) -> TokenStream2 {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::edu_json_parser::Parsable));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::edu_json_parser::Parsable for #name #ty_generics #where_clause {
+            fn parse_node(
+                json: &::edu_json_parser::Node
+            ) -> ::std::result::Result<Self, ::edu_json_parser::DecodeError> {
+                #body
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Field, Fields, Result};
+use syn::ext::IdentExt;
+use crate::attrs::{DefaultValue, FieldAttrs};
+
+// Objects for structs with named fields, arrays of the same length for tuple structs,
+// the inner value as it is for newtypes and null for unit structs. `name` is a path to
+// construct the value with, which is a variant for enums
+pub fn expand(name: &TokenStream, fields: &Fields) -> Result<TokenStream> {
+    match fields {
+        Fields::Named(named) => {
+            let values = named.named.iter()
+                .map(|field| {
+                    let ident = &field.ident;
+                    let key = ident.as_ref().map(|ident| ident.unraw().to_string()).unwrap_or_default();
+                    let value = field_value(field, key)?;
+                    Ok(quote!(#ident: #value))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote! {
+                if !json.is_object() {
+                    return ::std::result::Result::Err(
+                        ::edu_json_parser::DecodeError::wrong_type("object", json.kind())
+                    );
+                }
+                ::std::result::Result::Ok(#name { #(#values),* })
+            })
+        },
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+            let ty = &unnamed.unnamed[0].ty;
+            Ok(quote! {
+                ::std::result::Result::Ok(#name(<#ty as ::edu_json_parser::Parsable>::parse_node(json)?))
+            })
+        },
+        Fields::Unnamed(unnamed) => {
+            let len = unnamed.unnamed.len();
+            let values = unnamed.unnamed.iter().enumerate().map(|(i, field)| {
+                let ty = &field.ty;
+                quote!(json.get_parsed_at::<#ty>(#i)?)
+            });
+            Ok(quote! {
+                let found = json.try_len()
+                    .filter(|_| json.is_array())
+                    .ok_or_else(|| ::edu_json_parser::DecodeError::wrong_type("array", json.kind()))?;
+                if found != #len {
+                    return ::std::result::Result::Err(::edu_json_parser::DecodeError::wrong_length(#len, found));
+                }
+                ::std::result::Result::Ok(#name(#(#values),*))
+            })
+        },
+        Fields::Unit => Ok(quote! {
+            if !json.is_null() {
+                return ::std::result::Result::Err(
+                    ::edu_json_parser::DecodeError::wrong_type("null", json.kind())
+                );
+            }
+            ::std::result::Result::Ok(#name)
+        })
+    }
+}
+
+// An expression which reads one named field out of `json`
+fn field_value(field: &Field, name: String) -> Result<TokenStream> {
+    let attrs = FieldAttrs::parse(&field.attrs)?;
+    let ty = &field.ty;
+    if attrs.skip {
+        return Ok(quote!(::std::default::Default::default()));
+    }
+    if attrs.flatten {
+        return Ok(quote!(<#ty as ::edu_json_parser::Parsable>::parse_node(json)?));
+    }
+    let key = attrs.rename.unwrap_or(name);
+    let default = match attrs.default {
+        None => return Ok(quote!(json.get_parsed::<#ty>(#key)?)),
+        Some(DefaultValue::Trait) => quote!(::std::default::Default::default()),
+        Some(DefaultValue::Function(path)) => quote!(#path())
+    };
+    Ok(quote! {
+        match json.get_opt(#key)? {
+            ::std::option::Option::Some(node) => <#ty as ::edu_json_parser::Parsable>::parse_node(node)
+                .map_err(|e| e.in_field(#key))?,
+            ::std::option::Option::None => #default
+        }
+    })
+}
@@ -0,0 +1,44 @@
+use edu_json_parser::Parsable;
+
+// The same cards as in `sample2`, with `parse_node` written by the derive
+#[derive(Parsable, Debug)]
+pub struct CardData {
+    pub name: String,
+    pub last_name: String,
+    pub age: u32,
+    pub weight: f64,
+    pub sizes: [f64; 3],
+    #[parsable(default)]
+    pub nickname: Option<String>
+}
+
+const MANY_CARDS: &str =
+    r#"
+    [
+        {"name": "Santa", "last_name": "Clous", "age": 99, "weight": 150, "sizes":[120, 120, 120], "nickname": "Ho-ho" },
+        {"name": "John ", "last_name": "Snow", "age": 13, "weight": 60, "sizes":[90, 60, 90] }
+    ]
+    "#;
+
+const SPOILED_CARDS: &str =
+    r#"
+    [
+        {"name": "John ", "last_name": "Snow", "age": 13, "weight": 60, "sizes":[90, 60, 90] },
+        {"name": "Jazz", "last_name": "Spoily", "age": 10, "weight": 25, "sizes":[50, 40] }
+    ]
+    "#;
+
+fn main() {
+    match CardData::parse_array(MANY_CARDS) {
+        Ok(cards) => {
+            for (i, card) in cards.iter().enumerate() {
+                println!("Card {} contains: {:?}", i, card)
+            }
+        },
+        Err(error) => println!("Oops! Error on parse many cards! {}", error),
+    }
+    match CardData::parse_array(SPOILED_CARDS) {
+        Ok(cards) => println!("Spoiled cards are fine somehow: {:?}", cards),
+        Err(error) => println!("Oops! Error on parse spoiled cards! {}", error),
+    }
+}
@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate edu_json_parser;
 
-use edu_json_parser::{Node, Parsable};
+use edu_json_parser::{DecodeError, Node, Parsable};
 use std::fmt::{Display, Formatter};
 
 
@@ -9,7 +9,7 @@ use std::fmt::{Display, Formatter};
 pub struct CardData {
     pub name: String,
     pub last_name: String,
-    pub age: f64,
+    pub age: u32,
     pub weight: f64,
     pub sizes: [f64;3]
 }
@@ -29,49 +29,25 @@ impl Display for CardData {
 }
 
 impl Parsable for CardData {
-    fn parse_node(json: &Node) -> Result<Self, String> {
-        if !json.is_object() {
-            return Err("Node is not a dictionary".to_string());
-        }
-        match parse_many!(json =>
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+        // errors of the getters know their field, so `?` turns them into errors with a path
+        let (name, last_name, age, weight) = parse_many!(json =>
             get_string("name"),
             get_string("last_name"),
-            get_number("age"),
-            get_number("weight"),
-            get("sizes")
-        ) {
-            Ok((name, last_name, age, weight, sizes_node)) => {
-                let sizes = {
-                    if !sizes_node.is_array() {
-                        return Err(
-                            "failed to retrieve sizes: node is not array".to_string()
-                        );
-                    }
-                    if sizes_node.len() != 3 {
-                        return Err(
-                            "failed to retrieve sizes: length aren't equal 3".to_string()
-                        );
-                    }
-                    let breast_size = sizes_node[0].as_number();
-                    let belly_size = sizes_node[1].as_number();
-                    let booty_size = sizes_node[2].as_number();
-                    match (breast_size, belly_size, booty_size) {
-                        (Some(breast), Some(belly), Some(booty)) => [breast, belly, booty],
-                        _ => return Err(
-                            "failed to retrieve sizes: some of the array elements is not a number".to_string()
-                        )
-                    }
-                };
-                Ok(CardData{
-                    name: name.to_string(),
-                    last_name: last_name.to_string(),
-                    age,
-                    weight,
-                    sizes
-                })
-            },
-            Err(err_data) => Err(format!("parse failed: {}", err_data.to_string())),
+            get_u32("age"),
+            get_number("weight")
+        )?;
+        let sizes: Vec<f64> = json.get_parsed("sizes")?;
+        if sizes.len() != 3 {
+            return Err(DecodeError::new("sizes must be exactly 3 in length").in_field("sizes"));
         }
+        Ok(CardData{
+            name: name.to_string(),
+            last_name: last_name.to_string(),
+            age,
+            weight,
+            sizes: [sizes[0], sizes[1], sizes[2]]
+        })
     }
 }
 
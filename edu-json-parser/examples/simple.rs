@@ -45,32 +45,31 @@ fn parse_card(card: &str) {
                     ),
                 }
             );
-            if let Ok(sizes) = json_data.get("sizes") {
-                if sizes.is_array() {
-                    if sizes.len() != 3 {
-                        println!("Something wrong with sizes, they must be exactly 3 in length")
-                    } else {
-                        let breast_size = sizes[0].to_string()
-                            .unwrap_or("???".to_string());
+            // indexing never panics, missing values are just null
+            let sizes = &json_data["sizes"];
+            match sizes.try_len() {
+                Some(3) if sizes.is_array() => {
+                    let breast_size = sizes[0].to_string()
+                        .unwrap_or("???".to_string());
 
-                        let belly_size = sizes[1].to_string()
-                            .unwrap_or("???".to_string());
+                    let belly_size = sizes[1].to_string()
+                        .unwrap_or("???".to_string());
 
-                        let booty_size = sizes[2].to_string()
-                            .unwrap_or("???".to_string());
+                    let booty_size = sizes[2].to_string()
+                        .unwrap_or("???".to_string());
 
-                        println!(
-                            "My sizes are: breast={}, belly={}, booty={}",
-                            breast_size,
-                            belly_size,
-                            booty_size
-                        );
-                    }
-                } else {
-                    println!("It looks that somehow sizes aren't array!")
-                }
-            } else {
-                println!("It looks that I don't have sizes somehow. Maybe I don't even exist?")
+                    println!(
+                        "My sizes are: breast={}, belly={}, booty={}",
+                        breast_size,
+                        belly_size,
+                        booty_size
+                    );
+                },
+                Some(_) if sizes.is_array() =>
+                    println!("Something wrong with sizes, they must be exactly 3 in length"),
+                _ if sizes.is_null() =>
+                    println!("It looks that I don't have sizes somehow. Maybe I don't even exist?"),
+                _ => println!("It looks that somehow sizes aren't array!")
             }
         },
         Err(error_string) => println!("Error! {}", error_string)
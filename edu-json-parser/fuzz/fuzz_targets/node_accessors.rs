@@ -0,0 +1,52 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use edu_json_parser::{parse_json, Node};
+
+// Walks a parsed tree with every accessor. Only `len()` and `expect_*` may panic, so
+// they are kept to arrays, objects and bounds, the rest is called on any node
+fn walk(node: &Node) {
+    let _ = node.kind();
+    assert_eq!(node.try_len().is_some(), node.is_array() || node.is_object());
+    assert!(node["name"][0]["name"].is_null() || node.is_object());
+    assert_eq!(Node::Null, node[usize::MAX]);
+    let _ = node.is_null();
+    let _ = node.as_bool();
+    let _ = node.as_number();
+    let _ = node.as_string();
+    let _ = node.to_string();
+    let _ = node.to_json();
+    let _ = node.get_element_at(0);
+    let _ = node.get_element_at(usize::MAX);
+    let _ = node.get("");
+    let _ = node.get_string("name");
+    let _ = node.get_as_string("name");
+    let _ = node.get_number("name");
+    let _ = node.get_bool("name");
+    match node {
+        Node::Array(items) => {
+            assert_eq!(items.len(), node.len());
+            for i in 0..node.expect_len() {
+                assert_eq!(Ok(node.expect_at(i).clone()), node.get_element_at(i));
+                walk(&node[i]);
+            }
+            assert!(node.get_element_at(node.len()).is_err());
+            assert_eq!(Node::Null, node[node.len()]);
+        },
+        Node::Object(fields) => {
+            assert_eq!(fields.len(), node.len());
+            for key in fields.keys() {
+                assert_eq!(Ok(node.expect_key(key)), node.get(key));
+                walk(&node[key.as_str()]);
+            }
+        },
+        _ => {}
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(node) = parse_json(text) {
+            walk(&node);
+        }
+    }
+});
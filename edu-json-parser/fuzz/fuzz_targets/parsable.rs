@@ -0,0 +1,32 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use edu_json_parser::{parse_json, DecodeError, Node, Parsable};
+
+// The shape of `examples/sample2.rs`, whatever the input is only `Err` may come out of it
+#[derive(Debug)]
+struct Card {
+    name: String,
+    age: f64,
+    sizes: Vec<f64>
+}
+
+impl Parsable for Card {
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+        let name = json.get_string("name")?.to_string();
+        let age = json.get_number("age")?;
+        let sizes = json.get_parsed("sizes")?;
+        Ok(Card { name, age, sizes })
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(card) = Card::parse(text) {
+            let node = parse_json(text).unwrap();
+            assert_eq!(Ok(card.name.as_str()), node.get_string("name"));
+            assert_eq!(Ok(card.age.to_bits()), node.get_number("age").map(f64::to_bits));
+            assert_eq!(card.sizes.len(), node["sizes"].len());
+        }
+        let _ = Card::parse_array(text);
+    }
+});
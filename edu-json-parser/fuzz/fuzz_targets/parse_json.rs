@@ -0,0 +1,35 @@
+#![no_main]
+use std::str;
+use libfuzzer_sys::fuzz_target;
+use edu_json_parser::{
+    parse_json, parse_json_lenient, parse_json_simd, parse_json_simd_bytes, parse_json_borrowed,
+    parse_json_lazy, parse_json_bytes, parse_json_tolerant, parse_json_stream, parse_json_parallel,
+    parse_json_interned, parse_json_spanned, validate_json, CstDocument, Document, JsonParser, Tape
+};
+
+// No engine may panic on any input, and the ones which build a `Node` agree with `parse_json`
+fuzz_target!(|data: &[u8]| {
+    let _ = validate_json(data);
+    let _ = parse_json_bytes(data);
+    let _ = parse_json_simd_bytes(data);
+    let text = match str::from_utf8(data) {
+        Ok(text) => text,
+        Err(_) => return
+    };
+    let node = parse_json(text).ok();
+    assert_eq!(node, parse_json_simd(text).ok());
+    assert_eq!(node, parse_json_parallel(text).ok());
+    assert_eq!(node.is_some(), parse_json_borrowed(text).is_ok());
+    assert_eq!(node.is_some(), Tape::parse(text).is_ok());
+    assert_eq!(node.is_some(), Document::parse(text).is_ok());
+    assert_eq!(node.is_some(), CstDocument::parse(text).is_ok());
+    assert_eq!(node.is_some(), JsonParser::new().parse(text).is_ok());
+    if let Ok(lazy) = parse_json_lazy(text) {
+        let _ = lazy.to_node();
+    }
+    let _ = parse_json_lenient(text);
+    let _ = parse_json_interned(text);
+    let _ = parse_json_spanned(text);
+    let _ = parse_json_tolerant(text);
+    for _ in parse_json_stream(text) {}
+});
@@ -0,0 +1,14 @@
+#!/bin/sh
+# Seeds the corpus of every target with the JSON files of the tests and the benches:
+#   ./seed_corpus.sh && cargo fuzz run parse_json
+set -e
+cd "$(dirname "$0")"
+for target in parse_json parsable node_accessors; do
+    mkdir -p "corpus/$target"
+    # files of the tests and the benches are raw string literals for `include!`,
+    # so `r#"` and `"#` around the document are cut off
+    for file in ../tests/*.json ../benches/*.json; do
+        sed -e '1s/^r#*"//' -e '$s/"##*$//' "$file" > "corpus/$target/$(basename "$file")"
+    done
+    cp ../tests/JSONTestSuite/test_parsing/*.json "corpus/$target/"
+done
@@ -0,0 +1,310 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Index;
+use combine::{Parser, eof, attempt, sep_by, between};
+use combine::parser::char::char;
+use combine::parser::range::take_while;
+use crate::{Node, StringPiece, Grammar, string_part, json_space, bool_parser, number_parser, null_parser};
+use crate::errors::ErrorCause;
+use crate::errors::ErrorCause::*;
+
+static NULL: BorrowedNode<'static> = BorrowedNode::Null;
+
+// The same as `Node`, but strings without escapes are borrowed straight from the input
+#[derive(PartialEq, Clone, Debug)]
+pub enum BorrowedNode<'a>
+{
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(Cow<'a, str>),
+    Array(Vec<BorrowedNode<'a>>),
+    Object(HashMap<Cow<'a, str>, BorrowedNode<'a>>)
+}
+
+fn borrowed_string_inner<'a>() -> impl Parser<&'a str, Output = Cow<'a, str>> {
+    // most of strings have no escapes, so we try to just slice them first
+    let plain = c_hx_do! {
+        s <- take_while(|c: char| Grammar::Strict.is_plain_string_char(c)),
+        __ <- char('"');
+        Cow::Borrowed(s)
+    };
+    let escaped = c_hx_do! {
+        pieces <- string_part(Grammar::Strict),
+        __ <- char('"');
+        {
+            let pieces: Vec<StringPiece<'a>> = pieces;
+            let mut s = String::new();
+            for piece in pieces.iter() {
+                match piece {
+                    StringPiece::Ref(strref) => s.push_str(strref),
+                    StringPiece::Char(c) => if let Some(chr) = c { s.push(*chr); }
+                }
+            }
+            Cow::Owned(s)
+        }
+    };
+    c_hx_do! {
+        __ <- char('"'),
+        s <- attempt(plain).or(escaped);
+        s
+    }
+}
+
+fn borrowed_value_parser<'a>(depth: usize) -> impl Parser<&'a str, Output = BorrowedNode<'a>> {
+    let possible_parser = bool_parser()
+        .or(number_parser())
+        .or(null_parser())
+        .map(BorrowedNode::from)
+        .or(borrowed_string_inner().map(BorrowedNode::String))
+        .or(nested!(depth + 1, borrowed_array_parser(depth + 1)))
+        .or(nested!(depth + 1, borrowed_object_parser(depth + 1)));
+
+    c_hx_do! {
+        __ <- json_space(Grammar::Strict),
+        pars <- possible_parser,
+        ___ <- json_space(Grammar::Strict);
+        pars
+    }
+}
+
+fn borrowed_array_parser<'a>(depth: usize) -> impl Parser<&'a str, Output = BorrowedNode<'a>> {
+    between(
+        (char('['), json_space(Grammar::Strict)),
+        (json_space(Grammar::Strict), char(']')),
+        sep_by(borrowed_value_parser(depth), char(','))
+    ).map(|nodes: Vec<BorrowedNode<'a>>| BorrowedNode::Array(nodes))
+}
+
+fn borrowed_pair_parser<'a>(depth: usize) -> impl Parser<&'a str, Output = (Cow<'a, str>, BorrowedNode<'a>)> {
+    c_hx_do! {
+        __ <- json_space(Grammar::Strict),
+        l <- borrowed_string_inner(),
+        ___ <- json_space(Grammar::Strict),
+        ____ <- char(':'),
+        r <- borrowed_value_parser(depth);
+        (l, r)
+    }
+}
+
+fn borrowed_object_parser<'a>(depth: usize) -> impl Parser<&'a str, Output = BorrowedNode<'a>> {
+    between(
+        (char('{'), json_space(Grammar::Strict)),
+        (json_space(Grammar::Strict), char('}')),
+        sep_by(borrowed_pair_parser(depth), char(','))
+    ).map(|pairs: Vec<(Cow<'a, str>, BorrowedNode<'a>)>| {
+        let mut dict = HashMap::with_capacity(pairs.len());
+        for (l, r) in pairs {
+            dict.insert(l, r);
+        }
+        BorrowedNode::Object(dict)
+    })
+}
+
+pub fn parse_json_borrowed<'a>(content: &'a str) -> Result<BorrowedNode<'a>, String> {
+    let mut parser = c_hx_do! {
+        json <- borrowed_value_parser(0),
+        __ <- eof();
+        json
+    };
+    match parser.parse(content) {
+        Err(x) => Err(x.to_string()),
+        Ok((res, _)) => Ok(res)
+    }
+}
+
+impl<'a> From<Node> for BorrowedNode<'a> {
+    fn from(node: Node) -> Self {
+        match node {
+            Node::Null => BorrowedNode::Null,
+            Node::Boolean(b) => BorrowedNode::Boolean(b),
+            Node::Number(n) => BorrowedNode::Number(n),
+            Node::String(s) => BorrowedNode::String(Cow::Owned(s.to_string())),
+            Node::Array(arr) => BorrowedNode::Array(
+                arr.into_iter().map(BorrowedNode::from).collect()
+            ),
+            Node::Object(dict) => BorrowedNode::Object(
+                dict.into_iter()
+                    .map(|(k, v)| (Cow::Owned(k.to_string()), BorrowedNode::from(v)))
+                    .collect()
+            )
+        }
+    }
+}
+
+impl<'a> BorrowedNode<'a> {
+    pub fn to_owned(&self) -> Node {
+        match self {
+            BorrowedNode::Null => Node::Null,
+            BorrowedNode::Boolean(b) => Node::Boolean(*b),
+            BorrowedNode::Number(n) => Node::Number(*n),
+            BorrowedNode::String(s) => Node::String(s.as_ref().into()),
+            BorrowedNode::Array(arr) => Node::Array(
+                arr.iter().map(|item| item.to_owned()).collect()
+            ),
+            BorrowedNode::Object(dict) => Node::Object(
+                dict.iter().map(|(k, v)| (k.as_ref().into(), v.to_owned())).collect()
+            )
+        }
+    }
+
+    // true when neither the string itself nor anything inside needed a copy
+    pub fn is_fully_borrowed(&self) -> bool {
+        match self {
+            BorrowedNode::String(s) => matches!(s, Cow::Borrowed(_)),
+            BorrowedNode::Array(arr) => arr.iter().all(|item| item.is_fully_borrowed()),
+            BorrowedNode::Object(dict) => dict.iter().all(|(k, v)|
+                matches!(k, Cow::Borrowed(_)) && v.is_fully_borrowed()
+            ),
+            _ => true
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == BorrowedNode::Null
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            BorrowedNode::Boolean(b) => Some(*b),
+            _ => None
+        }
+    }
+
+    pub fn is_bool(&self) -> bool {
+        self.as_bool().is_some()
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            BorrowedNode::Number(n) => Some(*n),
+            _ => None
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.as_number().is_some()
+    }
+
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            BorrowedNode::String(s) => Some(s),
+            _ => None
+        }
+    }
+
+    // the same semantic as `Node::to_string`: strings, arrays and objects give None
+    pub fn to_string(&self) -> Option<String> {
+        match self {
+            BorrowedNode::Null => Some("null".to_string()),
+            BorrowedNode::Boolean(b) => Some(b.to_string()),
+            BorrowedNode::Number(n) => Some(n.to_string()),
+            _ => None
+        }
+    }
+
+    pub fn is_string(&self) -> bool {
+        self.as_string().is_some()
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<BorrowedNode<'a>>> {
+        match self {
+            BorrowedNode::Array(v) => Some(v),
+            _ => None
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.as_array().is_some()
+    }
+
+    pub fn as_object(&self) -> Option<&HashMap<Cow<'a, str>, BorrowedNode<'a>>> {
+        match self {
+            BorrowedNode::Object(d) => Some(d),
+            _ => None
+        }
+    }
+
+    pub fn is_object(&self) -> bool {
+        self.as_object().is_some()
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BorrowedNode::Null => "null",
+            BorrowedNode::Boolean(_) => "bool",
+            BorrowedNode::Number(_) => "number",
+            BorrowedNode::String(_) => "string",
+            BorrowedNode::Array(_) => "array",
+            BorrowedNode::Object(_) => "object"
+        }
+    }
+
+    pub fn try_len(&self) -> Option<usize> {
+        match self {
+            BorrowedNode::Array(v) => Some(v.len()),
+            BorrowedNode::Object(d) => Some(d.len()),
+            _ => None
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.try_len().expect("it appears that node is not array or dictionary so it has no len!")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.try_len() == Some(0)
+    }
+
+    fn element_at(&self, idx: usize) -> Result<&BorrowedNode<'a>, ErrorCause> {
+        match self.as_array() {
+            Some(arr) => arr.get(idx).ok_or(IndexOutOfBound(idx)),
+            None => Err(NodeIsNotArray)
+        }
+    }
+
+    pub fn get_element_at(&self, idx: usize) -> Result<BorrowedNode<'a>, ErrorCause> {
+        self.element_at(idx).cloned()
+    }
+
+    pub fn get(&self, key: &str) -> Result<&BorrowedNode<'a>, ErrorCause> {
+        match self {
+            BorrowedNode::Object(d) => d.get(key).ok_or_else(|| FieldNotExist(key.to_string())),
+            _ => Err(NodeIsNotADictionary)
+        }
+    }
+
+    node_getters! {
+        child: &BorrowedNode<'a>, element: element_at, string: &str, as_string: as_string
+    }
+
+    integer_getters! {
+        as_i8, get_i8: i8;
+        as_i16, get_i16: i16;
+        as_i32, get_i32: i32;
+        as_i64, get_i64: i64;
+        as_isize, get_isize: isize;
+        as_u8, get_u8: u8;
+        as_u16, get_u16: u16;
+        as_u32, get_u32: u32;
+        as_u64, get_u64: u64;
+        as_usize, get_usize: usize;
+    }
+}
+
+// missing values are `BorrowedNode::Null`, the same as for `Node`
+impl<'a> Index<&str> for BorrowedNode<'a>
+{
+    type Output = BorrowedNode<'a>;
+    fn index(&self, key: &str) -> &Self::Output {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+impl<'a> Index<usize> for BorrowedNode<'a>
+{
+    type Output = BorrowedNode<'a>;
+    fn index(&self, key: usize) -> &Self::Output {
+        self.as_array().and_then(|a| a.get(key)).unwrap_or(&NULL)
+    }
+}
@@ -0,0 +1,549 @@
+use std::collections::HashMap;
+use std::mem;
+use combine::{Parser, choice, attempt, eof, sep_by1};
+use combine::parser::char::char;
+use combine::parser::range::{take_while, recognize_with_value};
+use smol_str::SmolStr;
+use crate::{Node, Grammar, bool_parser, number_parser, string_parser, null_parser, string_parser_inner, is_json_whitespace};
+use crate::errors::ErrorCause;
+use crate::pointer::{split_pointer, parse_index};
+use crate::serializer::{write_node, write_string};
+
+// Lossless syntax tree: every token is kept as it was written and every piece of
+// whitespace between tokens is kept as well, so rendering gives the source back byte-for-byte
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct CstDocument {
+    pub(crate) leading: String,
+    pub(crate) root: CstValue,
+    pub(crate) trailing: String
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum CstValue {
+    Scalar { raw: String, node: Node },
+    Array(CstArray),
+    Object(CstObject)
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct CstArray {
+    pub(crate) items: Vec<CstElement>,
+    // whitespace between brackets of an empty array
+    pub(crate) inner: String
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub(crate) struct CstElement {
+    pub(crate) before: String,
+    pub(crate) value: CstValue,
+    pub(crate) after: String
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct CstObject {
+    pub(crate) members: Vec<CstMember>,
+    // whitespace between braces of an empty object
+    pub(crate) inner: String
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub(crate) struct CstMember {
+    pub(crate) before_key: String,
+    pub(crate) key_raw: String,
+    pub(crate) key: SmolStr,
+    pub(crate) after_key: String,
+    pub(crate) before_value: String,
+    pub(crate) value: CstValue,
+    pub(crate) after_value: String
+}
+
+fn trivia<'a>() -> impl Parser<&'a str, Output = String> {
+    take_while(is_json_whitespace).map(|s: &str| s.to_string())
+}
+
+fn cst_scalar_parser<'a>() -> impl Parser<&'a str, Output = CstValue> {
+    let scalar = bool_parser()
+        .or(number_parser())
+        .or(string_parser(Grammar::Strict))
+        .or(null_parser());
+    recognize_with_value(scalar).map(|(raw, node): (&str, Node)|
+        CstValue::Scalar { raw: raw.to_string(), node }
+    )
+}
+
+fn cst_value_parser<'a>(depth: usize) -> impl Parser<&'a str, Output = CstValue> {
+    cst_scalar_parser()
+        .or(nested!(depth + 1, cst_array_parser(depth + 1)))
+        .or(nested!(depth + 1, cst_object_parser(depth + 1)))
+}
+
+fn cst_element_parser<'a>(depth: usize) -> impl Parser<&'a str, Output = CstElement> {
+    c_hx_do! {
+        before <- trivia(),
+        value <- cst_value_parser(depth),
+        after <- trivia();
+        CstElement { before, value, after }
+    }
+}
+
+fn cst_array_parser<'a>(depth: usize) -> impl Parser<&'a str, Output = CstValue> {
+    let empty = c_hx_do! {
+        inner <- trivia(),
+        __ <- char(']');
+        CstArray { items: Vec::new(), inner }
+    };
+    let filled = c_hx_do! {
+        items <- sep_by1::<Vec<CstElement>, _, _, _>(cst_element_parser(depth), char(',')),
+        __ <- char(']');
+        CstArray { items, inner: String::new() }
+    };
+    c_hx_do! {
+        __ <- char('['),
+        arr <- choice((attempt(empty), filled));
+        CstValue::Array(arr)
+    }
+}
+
+fn cst_member_parser<'a>(depth: usize) -> impl Parser<&'a str, Output = CstMember> {
+    c_hx_do! {
+        before_key <- trivia(),
+        key <- recognize_with_value(string_parser_inner(Grammar::Strict)),
+        after_key <- trivia(),
+        __ <- char(':'),
+        before_value <- trivia(),
+        value <- cst_value_parser(depth),
+        after_value <- trivia();
+        CstMember {
+            before_key,
+            key_raw: key.0.to_string(),
+            key: key.1,
+            after_key,
+            before_value,
+            value,
+            after_value
+        }
+    }
+}
+
+fn cst_object_parser<'a>(depth: usize) -> impl Parser<&'a str, Output = CstValue> {
+    let empty = c_hx_do! {
+        inner <- trivia(),
+        __ <- char('}');
+        CstObject { members: Vec::new(), inner }
+    };
+    let filled = c_hx_do! {
+        members <- sep_by1::<Vec<CstMember>, _, _, _>(cst_member_parser(depth), char(',')),
+        __ <- char('}');
+        CstObject { members, inner: String::new() }
+    };
+    c_hx_do! {
+        __ <- char('{'),
+        obj <- choice((attempt(empty), filled));
+        CstValue::Object(obj)
+    }
+}
+
+fn cst_document_parser<'a>() -> impl Parser<&'a str, Output = CstDocument> {
+    c_hx_do! {
+        leading <- trivia(),
+        root <- cst_value_parser(0),
+        trailing <- trivia(),
+        __ <- eof();
+        CstDocument { leading, root, trailing }
+    }
+}
+
+impl CstValue {
+    // Parses a single value without surrounding whitespace, e.g. `1.50` or `{"a": [1]}`
+    pub fn parse_raw(raw: &str) -> Result<CstValue, ErrorCause> {
+        let mut parser = c_hx_do! {
+            value <- cst_value_parser(0),
+            __ <- eof();
+            value
+        };
+        match parser.parse(raw) {
+            Err(x) => Err(ErrorCause::MalformedJson(x.to_string())),
+            Ok((value, _)) => Ok(value)
+        }
+    }
+
+    // New values get the most compact spelling, keys of objects are sorted
+    pub fn from_node(node: &Node) -> CstValue {
+        match node {
+            Node::Array(arr) => CstValue::Array(CstArray {
+                items: arr.iter().map(|item| CstElement {
+                    before: String::new(),
+                    value: CstValue::from_node(item),
+                    after: String::new()
+                }).collect(),
+                inner: String::new()
+            }),
+            Node::Object(dict) => {
+                let mut keys: Vec<_> = dict.keys().collect();
+                keys.sort();
+                CstValue::Object(CstObject {
+                    members: keys.into_iter().map(|key| CstMember {
+                        before_key: String::new(),
+                        key_raw: raw_key(key),
+                        key: key.clone(),
+                        after_key: String::new(),
+                        before_value: String::new(),
+                        value: CstValue::from_node(&dict[key]),
+                        after_value: String::new()
+                    }).collect(),
+                    inner: String::new()
+                })
+            },
+            scalar => {
+                let mut raw = String::new();
+                write_node(&mut raw, scalar);
+                CstValue::Scalar { raw, node: scalar.clone() }
+            }
+        }
+    }
+
+    pub fn to_node(&self) -> Node {
+        match self {
+            CstValue::Scalar { node, .. } => node.clone(),
+            CstValue::Array(arr) => Node::Array(
+                arr.items.iter().map(|item| item.value.to_node()).collect()
+            ),
+            CstValue::Object(obj) => {
+                let mut dict = HashMap::with_capacity(obj.members.len());
+                for member in obj.members.iter() {
+                    dict.insert(member.key.clone(), member.value.to_node());
+                }
+                Node::Object(dict)
+            }
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.render_into(&mut out);
+        out
+    }
+
+    fn render_into(&self, out: &mut String) {
+        match self {
+            CstValue::Scalar { raw, .. } => out.push_str(raw),
+            CstValue::Array(arr) => {
+                out.push('[');
+                if arr.items.is_empty() {
+                    out.push_str(&arr.inner);
+                }
+                for (i, item) in arr.items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&item.before);
+                    item.value.render_into(out);
+                    out.push_str(&item.after);
+                }
+                out.push(']');
+            },
+            CstValue::Object(obj) => {
+                out.push('{');
+                if obj.members.is_empty() {
+                    out.push_str(&obj.inner);
+                }
+                for (i, member) in obj.members.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&member.before_key);
+                    out.push_str(&member.key_raw);
+                    out.push_str(&member.after_key);
+                    out.push(':');
+                    out.push_str(&member.before_value);
+                    member.value.render_into(out);
+                    out.push_str(&member.after_value);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    // Keys in the order they were written, duplicates included
+    pub fn keys(&self) -> Vec<&str> {
+        match self {
+            CstValue::Object(obj) => obj.members.iter().map(|m| m.key.as_str()).collect(),
+            _ => Vec::new()
+        }
+    }
+
+    fn child(&self, token: &str) -> Result<&CstValue, ErrorCause> {
+        match self {
+            CstValue::Object(obj) => match obj.position(token) {
+                Some(idx) => Ok(&obj.members[idx].value),
+                None => Err(ErrorCause::FieldNotExist(token.to_string()))
+            },
+            CstValue::Array(arr) => {
+                let idx = parse_index(token)?;
+                match arr.items.get(idx) {
+                    Some(item) => Ok(&item.value),
+                    None => Err(ErrorCause::IndexOutOfBound(idx))
+                }
+            },
+            _ => Err(ErrorCause::FieldNotExist(token.to_string()))
+        }
+    }
+
+    fn child_mut(&mut self, token: &str) -> Result<&mut CstValue, ErrorCause> {
+        match self {
+            CstValue::Object(obj) => match obj.position(token) {
+                Some(idx) => Ok(&mut obj.members[idx].value),
+                None => Err(ErrorCause::FieldNotExist(token.to_string()))
+            },
+            CstValue::Array(arr) => {
+                let idx = parse_index(token)?;
+                match arr.items.get_mut(idx) {
+                    Some(item) => Ok(&mut item.value),
+                    None => Err(ErrorCause::IndexOutOfBound(idx))
+                }
+            },
+            _ => Err(ErrorCause::FieldNotExist(token.to_string()))
+        }
+    }
+}
+
+fn raw_key(key: &str) -> String {
+    let mut raw = String::with_capacity(key.len() + 2);
+    write_string(&mut raw, key);
+    raw
+}
+
+impl CstObject {
+    // the last one wins on duplicates, the same way it works for `Node`
+    fn position(&self, key: &str) -> Option<usize> {
+        self.members.iter().rposition(|m| m.key == key)
+    }
+
+    fn insert(&mut self, key: &str, value: CstValue) {
+        if let Some(idx) = self.position(key) {
+            self.members[idx].value = value;
+            return;
+        }
+        let mut member = CstMember {
+            before_key: String::new(),
+            key_raw: raw_key(key),
+            key: SmolStr::new(key),
+            after_key: String::new(),
+            before_value: String::new(),
+            value,
+            after_value: String::new()
+        };
+        // new member mimics formatting of the last one, and takes over
+        // the whitespace which was in front of the closing brace
+        let len = self.members.len();
+        if len > 0 {
+            let previous_after = if len > 1 {
+                self.members[len - 2].after_value.clone()
+            } else {
+                String::new()
+            };
+            let last = &mut self.members[len - 1];
+            member.before_key = if len == 1 && !last.before_key.contains('\n') {
+                // whitespace after `{` tells nothing about a one-line object,
+                // so we guess by the whitespace after `:`
+                if last.before_value.is_empty() { String::new() } else { " ".to_string() }
+            } else {
+                last.before_key.clone()
+            };
+            member.after_key = last.after_key.clone();
+            member.before_value = last.before_value.clone();
+            member.after_value = mem::replace(&mut last.after_value, previous_after);
+        }
+        self.members.push(member);
+    }
+
+    fn remove(&mut self, key: &str) -> Result<CstValue, ErrorCause> {
+        let idx = self.position(key)
+            .ok_or_else(|| ErrorCause::FieldNotExist(key.to_string()))?;
+        let removed = self.members.remove(idx);
+        // the next member takes the place of the removed one, with the whitespace after `{`
+        if idx == 0 {
+            if let Some(first) = self.members.first_mut() {
+                first.before_key = removed.before_key;
+            }
+        }
+        if idx == self.members.len() {
+            match self.members.last_mut() {
+                Some(last) => last.after_value = removed.after_value,
+                None => self.inner = removed.after_value
+            }
+        }
+        Ok(removed.value)
+    }
+}
+
+impl CstArray {
+    fn push(&mut self, value: CstValue) {
+        let mut element = CstElement {
+            before: String::new(),
+            value,
+            after: String::new()
+        };
+        let len = self.items.len();
+        if len > 0 {
+            let previous_after = if len > 1 {
+                self.items[len - 2].after.clone()
+            } else {
+                String::new()
+            };
+            let last = &mut self.items[len - 1];
+            element.before = last.before.clone();
+            element.after = mem::replace(&mut last.after, previous_after);
+        }
+        self.items.push(element);
+    }
+
+    // A new element mimics the one it is put in front of, which moves one place further
+    fn insert(&mut self, idx: usize, value: CstValue) -> Result<(), ErrorCause> {
+        let len = self.items.len();
+        if idx > len {
+            return Err(ErrorCause::IndexOutOfBound(idx));
+        }
+        if idx == len {
+            self.push(value);
+            return Ok(());
+        }
+        let element = CstElement {
+            before: self.items[idx].before.clone(),
+            value,
+            // whitespace before a comma, the first element is never the last one here
+            after: if len > 1 { self.items[0].after.clone() } else { String::new() }
+        };
+        // the old first element is not right after `[` anymore
+        if idx == 0 && len > 1 {
+            self.items[0].before = self.items[1].before.clone();
+        }
+        self.items.insert(idx, element);
+        Ok(())
+    }
+
+    fn remove(&mut self, idx: usize) -> Result<CstValue, ErrorCause> {
+        if idx >= self.items.len() {
+            return Err(ErrorCause::IndexOutOfBound(idx));
+        }
+        let removed = self.items.remove(idx);
+        if idx == 0 {
+            if let Some(first) = self.items.first_mut() {
+                first.before = removed.before;
+            }
+        }
+        if idx == self.items.len() {
+            match self.items.last_mut() {
+                Some(last) => last.after = removed.after,
+                None => self.inner = removed.after
+            }
+        }
+        Ok(removed.value)
+    }
+}
+
+impl CstDocument {
+    pub fn parse(content: &str) -> Result<CstDocument, String> {
+        match cst_document_parser().parse(content) {
+            Err(x) => Err(x.to_string()),
+            Ok((document, _)) => Ok(document)
+        }
+    }
+
+    pub fn root(&self) -> &CstValue {
+        &self.root
+    }
+
+    pub fn to_node(&self) -> Node {
+        self.root.to_node()
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::with_capacity(self.leading.len() + self.trailing.len());
+        out.push_str(&self.leading);
+        self.root.render_into(&mut out);
+        out.push_str(&self.trailing);
+        out
+    }
+
+    pub fn get(&self, pointer: &str) -> Result<&CstValue, ErrorCause> {
+        let mut current = &self.root;
+        for token in split_pointer(pointer)?.iter() {
+            current = current.child(token)?;
+        }
+        Ok(current)
+    }
+
+    fn get_mut(&mut self, tokens: &[String]) -> Result<&mut CstValue, ErrorCause> {
+        let mut current = &mut self.root;
+        for token in tokens.iter() {
+            current = current.child_mut(token)?;
+        }
+        Ok(current)
+    }
+
+    // Replaces an existing value, whitespace around it stays untouched
+    pub fn set(&mut self, pointer: &str, value: &Node) -> Result<(), ErrorCause> {
+        self.set_value(pointer, CstValue::from_node(value))
+    }
+
+    // Same as `set`, but keeps the spelling given by user, e.g. `1.0e3` instead of `1000`
+    pub fn set_raw(&mut self, pointer: &str, raw: &str) -> Result<(), ErrorCause> {
+        self.set_value(pointer, CstValue::parse_raw(raw)?)
+    }
+
+    fn set_value(&mut self, pointer: &str, value: CstValue) -> Result<(), ErrorCause> {
+        let tokens = split_pointer(pointer)?;
+        *self.get_mut(&tokens)? = value;
+        Ok(())
+    }
+
+    // Adds a key to the object found by the pointer, or replaces its value if the key exists
+    pub fn insert(&mut self, pointer: &str, key: &str, value: &Node) -> Result<(), ErrorCause> {
+        let tokens = split_pointer(pointer)?;
+        match self.get_mut(&tokens)? {
+            CstValue::Object(obj) => {
+                obj.insert(key, CstValue::from_node(value));
+                Ok(())
+            },
+            _ => Err(ErrorCause::NodeIsNotADictionary)
+        }
+    }
+
+    pub fn push(&mut self, pointer: &str, value: &Node) -> Result<(), ErrorCause> {
+        let tokens = split_pointer(pointer)?;
+        match self.get_mut(&tokens)? {
+            CstValue::Array(arr) => {
+                arr.push(CstValue::from_node(value));
+                Ok(())
+            },
+            _ => Err(ErrorCause::NodeIsNotArray)
+        }
+    }
+
+    // Puts a value in front of the element `idx` of the array found by the pointer,
+    // `idx` equal to the length of the array is the same as `push`
+    pub fn insert_at(&mut self, pointer: &str, idx: usize, value: &Node) -> Result<(), ErrorCause> {
+        let tokens = split_pointer(pointer)?;
+        match self.get_mut(&tokens)? {
+            CstValue::Array(arr) => arr.insert(idx, CstValue::from_node(value)),
+            _ => Err(ErrorCause::NodeIsNotArray)
+        }
+    }
+
+    // Removes an object member or an array element together with its separating comma
+    pub fn remove(&mut self, pointer: &str) -> Result<Node, ErrorCause> {
+        let tokens = split_pointer(pointer)?;
+        let (last_token, parent_tokens) = match tokens.split_last() {
+            Some(split) => split,
+            None => return Err(ErrorCause::InvalidPointer(pointer.to_string()))
+        };
+        let removed = match self.get_mut(parent_tokens)? {
+            CstValue::Object(obj) => obj.remove(last_token)?,
+            CstValue::Array(arr) => arr.remove(parse_index(last_token)?)?,
+            _ => return Err(ErrorCause::FieldNotExist(last_token.to_string()))
+        };
+        Ok(removed.to_node())
+    }
+}
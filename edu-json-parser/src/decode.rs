@@ -0,0 +1,132 @@
+use std::fmt::{Display, Formatter};
+use crate::errors::ErrorCause;
+use crate::pointer::escape_pointer_token;
+use crate::span::{Span, SpanTable};
+
+// Error of `Parsable`: the JSON Pointer of the value which could not be decoded, relative
+// to the node given to `parse_node`, and why
+#[derive(PartialEq, Clone, Debug)]
+pub struct DecodeError {
+    pub pointer: String,
+    pub message: String,
+    // both are set when a value is of a wrong type
+    pub expected: Option<&'static str>,
+    pub found: Option<&'static str>,
+    // only known when decoding has started from text, boxed to keep the error small
+    pub span: Option<Box<Span>>
+}
+
+impl DecodeError {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        DecodeError {
+            pointer: String::new(),
+            message: message.into(),
+            expected: None,
+            found: None,
+            span: None
+        }
+    }
+
+    pub fn wrong_type(expected: &'static str, found: &'static str) -> Self {
+        DecodeError {
+            expected: Some(expected),
+            found: Some(found),
+            ..DecodeError::new(format!("expected {}, found {}", expected, found))
+        }
+    }
+
+    // for tuples and fixed size arrays
+    pub fn wrong_length(expected: usize, found: usize) -> Self {
+        DecodeError::new(format!("expected an array of {} elements, found {}", expected, found))
+    }
+
+    // A tag of an enum which names none of its variants
+    pub fn unknown_variant(found: &str, expected: &[&str]) -> Self {
+        let expected = expected.iter()
+            .map(|name| format!("`{}`", name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        DecodeError::new(format!("unknown variant `{}`, expected one of {}", found, expected))
+    }
+
+    // An untagged enum, with an error of every variant which has been tried
+    pub fn no_matching_variant(enum_name: &str, tried: Vec<(&str, DecodeError)>) -> Self {
+        let tried = tried.iter()
+            .map(|(name, error)| format!("`{}`: {}", name, error))
+            .collect::<Vec<_>>()
+            .join("; ");
+        DecodeError::new(format!("no variant of `{}` matches, tried {}", enum_name, tried))
+    }
+
+    // Both move the error one level down, for a value which has failed inside a field or an element
+    pub fn in_field(mut self, key: &str) -> Self {
+        self.pointer = format!("/{}{}", escape_pointer_token(key), self.pointer);
+        self
+    }
+
+    pub fn in_element(mut self, idx: usize) -> Self {
+        self.pointer = format!("/{}{}", idx, self.pointer);
+        self
+    }
+
+    // `as_*` getters have no field, they report an empty name
+    fn in_named_field(self, field: &str) -> Self {
+        if field.is_empty() {
+            self
+        } else {
+            self.in_field(field)
+        }
+    }
+
+    // Span of the value at the pointer, or of the closest parent if there is no such value
+    pub fn with_spans(mut self, spans: &SpanTable) -> Self {
+        let mut pointer = self.pointer.as_str();
+        loop {
+            if let Some(span) = spans.value_span(pointer) {
+                self.span = Some(Box::new(span));
+                break;
+            }
+            match pointer.rfind('/') {
+                Some(slash) => pointer = &pointer[..slash],
+                None => break
+            }
+        }
+        self
+    }
+}
+
+// Getters report a field they were asked for, so it goes to the pointer
+impl From<ErrorCause> for DecodeError {
+    fn from(cause: ErrorCause) -> Self {
+        let message = cause.to_string();
+        match cause {
+            ErrorCause::FieldNotExist(field) => DecodeError::new(message).in_field(&field),
+            ErrorCause::WrongTypeRequested(field, type_name, found) => DecodeError {
+                expected: Some(type_name),
+                found: Some(found),
+                ..DecodeError::new(message).in_named_field(&field)
+            },
+            ErrorCause::NotAnInteger(field, _, type_name)
+            | ErrorCause::NumberOutOfRange(field, _, type_name)
+            | ErrorCause::InexactNumber(field, _, type_name) => DecodeError {
+                expected: Some(type_name),
+                found: Some("number"),
+                ..DecodeError::new(message).in_named_field(&field)
+            },
+            ErrorCause::IndexOutOfBound(idx) => DecodeError::new(message).in_element(idx),
+            _ => DecodeError::new(message)
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.span {
+            Some(span) => write!(
+                f, "{} (at '{}', line {}, column {})",
+                self.message, self.pointer, span.start.line, span.start.column
+            ),
+            None => write!(f, "{} (at '{}')", self.message, self.pointer)
+        }
+    }
+}
@@ -1,8 +1,36 @@
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use crate::errors::ErrorCause;
 use crate::errors::ErrorCause::*;
 use std::ops::Index;
 use smol_str::SmolStr;
+use crate::serializer::write_node;
+use crate::traits::Parsable;
+use crate::decode::DecodeError;
+
+// Above this not every integer has an f64 of its own, so the text may have been rounded
+const MAX_EXACT_INTEGER: f64 = 9007199254740991.0;
+
+// Numbers which are whole and fit into `T`. Every f64 without a fraction fits into i128
+// unless it is huge, and then the cast saturates, which is out of range anyway
+pub(crate) fn integer<T: TryFrom<i128>>(key: &str, n: f64, type_name: &'static str) -> Result<T, ErrorCause> {
+    if !n.is_finite() || n.fract() != 0.0 {
+        return Err(NotAnInteger(key.to_string(), n, type_name));
+    }
+    if n.abs() <= MAX_EXACT_INTEGER {
+        return T::try_from(n as i128).map_err(|_| NumberOutOfRange(key.to_string(), n, type_name));
+    }
+    // The text could have been anything up to the next f64 towards zero, like `u64::MAX`
+    // which is rounded up to 2^64
+    let closer = f64::from_bits(n.to_bits() - 1);
+    match T::try_from(closer as i128) {
+        Ok(_) => Err(InexactNumber(key.to_string(), n, type_name)),
+        Err(_) => Err(NumberOutOfRange(key.to_string(), n, type_name))
+    }
+}
+
+// What `Index` gives for a missing key, an index out of bounds or a node of a wrong type
+static NULL: Node = Node::Null;
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum Node
@@ -198,6 +226,12 @@ impl Node {
         }
     }
 
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        write_node(&mut out, self);
+        out
+    }
+
     pub fn is_string(&self) -> bool {
         if let Node::String(_) = self {
             true
@@ -230,12 +264,41 @@ impl Node {
         self.as_object().map(|_| true).unwrap_or(false)
     }
 
-    pub fn len(&self) -> usize {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Node::Null => "null",
+            Node::Boolean(_) => "bool",
+            Node::Number(_) => "number",
+            Node::String(_) => "string",
+            Node::Array(_) => "array",
+            Node::Object(_) => "object"
+        }
+    }
+
+    pub fn try_len(&self) -> Option<usize> {
         match self {
             Node::Array(v) => Some(v.len()),
             Node::Object(d) => Some(d.len()),
             _ => None
-        }.expect("it appears that node is not array or dictionary so it has no len!")
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.try_len().expect("it appears that node is not array or dictionary so it has no len!")
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Node::String(s) => Some(s),
+            _ => None
+        }
+    }
+
+    fn element_at(&self, idx: usize) -> Result<&Node, ErrorCause> {
+        match self.as_array() {
+            Some(arr) => arr.get(idx).ok_or(IndexOutOfBound(idx)),
+            None => Err(NodeIsNotArray)
+        }
     }
 
     pub fn get_element_at(&self, idx: usize) -> Result<Node, ErrorCause> {
@@ -262,57 +325,116 @@ impl Node {
         }
     }
 
-    pub fn get_string(&self, key: &str) -> Result<&str, ErrorCause> {
-        match self.get(key) {
-            Err(e) => Err(e),
-            Ok(node) => match node {
-                Node::String(s) => Ok(s),
-                _ => Err(ErrorCause::WrongTypeRequested(key.to_string(), "string"))
-            },
-        }
+    node_getters! {
+        child: &Node, element: element_at, string: &str, as_string: as_str
+    }
+
+    pub fn get_array(&self, key: &str) -> Result<&Vec<Node>, ErrorCause> {
+        let node = self.get(key)?;
+        node.as_array()
+            .ok_or_else(|| WrongTypeRequested(key.to_string(), "array", node.kind()))
+    }
+
+    pub fn get_object(&self, key: &str) -> Result<&HashMap<SmolStr, Node>, ErrorCause> {
+        let node = self.get(key)?;
+        node.as_object()
+            .ok_or_else(|| WrongTypeRequested(key.to_string(), "object", node.kind()))
     }
 
-    pub fn get_as_string(&self, key: &str) -> Result<String, ErrorCause> {
+    // Every element goes through `T::parse_node`, the first one which fails gives its own error
+    // with a path like `/sizes/2`
+    pub fn get_array_of<T: Parsable>(&self, key: &str) -> Result<Vec<T>, DecodeError> {
+        self.get_parsed::<Vec<T>>(key)
+    }
+
+    // Decodes a field with `T::parse_node`, errors of nested values come with a full path
+    // like `/sizes/2`. A missing field is taken for null when `T` accepts it, like `Option` does
+    pub fn get_parsed<T: Parsable>(&self, key: &str) -> Result<T, DecodeError> {
         match self.get(key) {
-            Err(e) => Err(e),
-            Ok(node) => match node.to_string() {
-                Some(data) => Ok(data),
-                None => Err(ErrorCause::WrongTypeRequested(key.to_string(), "as string")),
-            },
+            Ok(node) => T::parse_node(node).map_err(|e| e.in_field(key)),
+            Err(FieldNotExist(_)) => T::parse_node(&NULL)
+                .map_err(|_| DecodeError::from(FieldNotExist(key.to_string()))),
+            Err(cause) => Err(cause.into())
         }
     }
 
-    pub fn get_number(&self, key: &str) -> Result<f64, ErrorCause> {
-        match self.get(key) {
-            Err(e) => Err(e),
-            Ok(node) => match node.as_number() {
-                None => Err(ErrorCause::WrongTypeRequested(key.to_string(), "number")),
-                Some(data) => Ok(data),
+    pub fn get_parsed_at<T: Parsable>(&self, idx: usize) -> Result<T, DecodeError> {
+        match self.as_array() {
+            Some(arr) => match arr.get(idx) {
+                Some(node) => T::parse_node(node).map_err(|e| e.in_element(idx)),
+                None => Err(IndexOutOfBound(idx).into())
             },
+            None => Err(DecodeError::wrong_type("array", self.kind()))
         }
     }
 
-    pub fn get_bool(&self, key: &str) -> Result<bool, ErrorCause> {
+    // Optional fields: a missing field and a null are both None, a value of a wrong
+    // type is still an error
+    pub fn get_opt(&self, key: &str) -> Result<Option<&Node>, ErrorCause> {
         match self.get(key) {
-            Err(e) => Err(e),
-            Ok(node) => match node.as_bool() {
-                None => Err(ErrorCause::WrongTypeRequested(key.to_string(), "bool")),
-                Some(data) => Ok(data),
-            },
+            Ok(Node::Null) | Err(FieldNotExist(_)) => Ok(None),
+            Ok(node) => Ok(Some(node)),
+            Err(e) => Err(e)
         }
     }
+
+    pub fn get_opt_string(&self, key: &str) -> Result<Option<&str>, ErrorCause> {
+        self.get_opt(key)?.map(|_| self.get_string(key)).transpose()
+    }
+
+    pub fn get_opt_number(&self, key: &str) -> Result<Option<f64>, ErrorCause> {
+        self.get_opt(key)?.map(|_| self.get_number(key)).transpose()
+    }
+
+    pub fn get_opt_bool(&self, key: &str) -> Result<Option<bool>, ErrorCause> {
+        self.get_opt(key)?.map(|_| self.get_bool(key)).transpose()
+    }
+
+    pub fn get_opt_array(&self, key: &str) -> Result<Option<&Vec<Node>>, ErrorCause> {
+        self.get_opt(key)?.map(|_| self.get_array(key)).transpose()
+    }
+
+    pub fn get_opt_object(&self, key: &str) -> Result<Option<&HashMap<SmolStr, Node>>, ErrorCause> {
+        self.get_opt(key)?.map(|_| self.get_object(key)).transpose()
+    }
+
+    // The same as `get_opt_*`, with a default instead of None
+    pub fn get_node_or<'a>(&'a self, key: &str, default: &'a Node) -> Result<&'a Node, ErrorCause> {
+        Ok(self.get_opt(key)?.unwrap_or(default))
+    }
+
+    pub fn get_string_or<'a>(&'a self, key: &str, default: &'a str) -> Result<&'a str, ErrorCause> {
+        Ok(self.get_opt_string(key)?.unwrap_or(default))
+    }
+
+    pub fn get_number_or(&self, key: &str, default: f64) -> Result<f64, ErrorCause> {
+        Ok(self.get_opt_number(key)?.unwrap_or(default))
+    }
+
+    pub fn get_bool_or(&self, key: &str, default: bool) -> Result<bool, ErrorCause> {
+        Ok(self.get_opt_bool(key)?.unwrap_or(default))
+    }
+
+    integer_getters! {
+        as_i8, get_i8: i8;
+        as_i16, get_i16: i16;
+        as_i32, get_i32: i32;
+        as_i64, get_i64: i64;
+        as_isize, get_isize: isize;
+        as_u8, get_u8: u8;
+        as_u16, get_u16: u16;
+        as_u32, get_u32: u32;
+        as_u64, get_u64: u64;
+        as_usize, get_usize: usize;
+    }
 }
 
+// Like in serde_json, a missing value is `Node::Null`, so `node["a"][0]["b"]` never panics
 impl Index<&str> for Node
 {
     type Output = Node;
     fn index(&self, key: &str) -> &Self::Output {
-        match self {
-            Node::Object(d) => {
-                &d[key]
-            },
-            _ => panic!("fail")
-        }
+        self.get(key).unwrap_or(&NULL)
     }
 }
 
@@ -320,11 +442,6 @@ impl Index<usize> for Node
 {
     type Output = Node;
     fn index(&self, key: usize) -> &Self::Output {
-        match self {
-            Node::Array(a) => {
-                &a[key]
-            },
-            _ => panic!("fail")
-        }
+        self.as_array().and_then(|a| a.get(key)).unwrap_or(&NULL)
     }
 }
\ No newline at end of file
@@ -0,0 +1,447 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem;
+use crate::Node;
+use crate::errors::ErrorCause;
+use crate::errors::ErrorCause::*;
+use crate::sink::{Sink, SinkState, parse_into};
+
+// All values of one parse live in a handful of flat buffers owned by the document:
+// string bytes, a value table, array elements and object entries. Views refer to them
+// by index, so dropping a document is just freeing these buffers, no recursive walk needed.
+// Object entries keep the order they were written, but only the last one of duplicate keys
+// is kept, as it is for `Node`
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+enum Value {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String { start: u32, len: u32 },
+    Array { first: u32, len: u32 },
+    Object { first: u32, len: u32 }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+struct Entry {
+    key_start: u32,
+    key_len: u32,
+    value: u32
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Document {
+    strings: String,
+    values: Vec<Value>,
+    elements: Vec<u32>,
+    entries: Vec<Entry>,
+    // indices of entries of every object sorted by key, at the same place as its entries
+    sorted: Vec<u32>,
+    root: u32
+}
+
+#[derive(Default)]
+struct DocumentBuilder {
+    strings: String,
+    values: Vec<Value>,
+    elements: Vec<u32>,
+    entries: Vec<Entry>,
+    sorted: Vec<u32>,
+    // values which are not yet attached to their array or object
+    pending_values: Vec<u32>,
+    pending_keys: Vec<(u32, u32)>,
+    // where pending values (and keys) of currently open containers start
+    frames: Vec<(usize, usize)>
+}
+
+impl DocumentBuilder {
+    fn push_value(&mut self, value: Value) {
+        self.pending_values.push(self.values.len() as u32);
+        self.values.push(value);
+    }
+
+    fn push_str(&mut self, s: &str) -> (u32, u32) {
+        let start = self.strings.len() as u32;
+        self.strings.push_str(s);
+        (start, s.len() as u32)
+    }
+
+    // Takes buffers of a document, they keep their capacity
+    fn reusing(doc: &mut Document) -> Self {
+        let mut builder = DocumentBuilder {
+            strings: mem::take(&mut doc.strings),
+            values: mem::take(&mut doc.values),
+            elements: mem::take(&mut doc.elements),
+            entries: mem::take(&mut doc.entries),
+            sorted: mem::take(&mut doc.sorted),
+            ..DocumentBuilder::default()
+        };
+        builder.clear();
+        builder
+    }
+
+    fn clear(&mut self) {
+        self.strings.clear();
+        self.values.clear();
+        self.elements.clear();
+        self.entries.clear();
+        self.sorted.clear();
+        self.pending_values.clear();
+        self.pending_keys.clear();
+        self.frames.clear();
+    }
+
+    fn finish(mut self) -> Document {
+        let root = self.pending_values.pop().unwrap_or_default();
+        Document {
+            strings: self.strings,
+            values: self.values,
+            elements: self.elements,
+            entries: self.entries,
+            sorted: self.sorted,
+            root
+        }
+    }
+
+    fn open(&mut self) {
+        self.frames.push((self.pending_values.len(), self.pending_keys.len()));
+    }
+
+    // Fills `sorted` for entries of an object which start at `first`. When some keys are
+    // the same, earlier entries with them are dropped and it starts over
+    fn sort_entries(&mut self, first: usize) {
+        let DocumentBuilder { strings, entries, sorted, .. } = self;
+        sorted.truncate(first);
+        sorted.extend(first as u32..entries.len() as u32);
+        // stable, so entries with the same key stay in the order they were written
+        sorted[first..].sort_by(|&a, &b| entry_key(strings, &entries[a as usize]).cmp(entry_key(strings, &entries[b as usize])));
+        let mut dropped = vec![];
+        for pair in sorted[first..].windows(2) {
+            if entry_key(strings, &entries[pair[0] as usize]) == entry_key(strings, &entries[pair[1] as usize]) {
+                dropped.push(pair[0] as usize);
+            }
+        }
+        if !dropped.is_empty() {
+            dropped.sort_unstable();
+            let mut idx = 0;
+            entries.retain(|_| {
+                idx += 1;
+                dropped.binary_search(&(idx - 1)).is_err()
+            });
+            self.sort_entries(first);
+        }
+    }
+}
+
+fn entry_key<'s>(strings: &'s str, entry: &Entry) -> &'s str {
+    &strings[entry.key_start as usize..(entry.key_start + entry.key_len) as usize]
+}
+
+impl Sink for DocumentBuilder {
+    fn scalar(&mut self, node: Node) {
+        self.push_value(match node {
+            Node::Boolean(b) => Value::Boolean(b),
+            Node::Number(n) => Value::Number(n),
+            _ => Value::Null
+        });
+    }
+
+    fn string(&mut self, s: &str) {
+        let (start, len) = self.push_str(s);
+        self.push_value(Value::String { start, len });
+    }
+
+    fn key(&mut self, k: &str) {
+        let key = self.push_str(k);
+        self.pending_keys.push(key);
+    }
+
+    fn start_array(&mut self) {
+        self.open();
+    }
+
+    fn end_array(&mut self) {
+        let (values_start, _) = self.frames.pop().unwrap_or_default();
+        let first = self.elements.len() as u32;
+        let len = (self.pending_values.len() - values_start) as u32;
+        self.elements.extend(self.pending_values.drain(values_start..));
+        self.push_value(Value::Array { first, len });
+    }
+
+    fn start_object(&mut self) {
+        self.open();
+    }
+
+    fn end_object(&mut self) {
+        let (values_start, keys_start) = self.frames.pop().unwrap_or_default();
+        let first = self.entries.len();
+        let keys = self.pending_keys.drain(keys_start..);
+        let values = self.pending_values.drain(values_start..);
+        self.entries.extend(keys.zip(values).map(|((key_start, key_len), value)|
+            Entry { key_start, key_len, value }
+        ));
+        self.sort_entries(first);
+        let len = (self.entries.len() - first) as u32;
+        self.push_value(Value::Object { first: first as u32, len });
+    }
+}
+
+// The builder comes back even on an error, so its buffers are not lost
+fn build(content: &str, builder: DocumentBuilder) -> (Result<(), String>, DocumentBuilder) {
+    // every offset inside fits into u32 as long as the input does
+    if content.len() > u32::MAX as usize {
+        return (Err("document is too large".to_string()), builder);
+    }
+    let state = RefCell::new(SinkState::new(builder, String::new()));
+    let result = parse_into(content, &state);
+    (result, state.into_inner().sink)
+}
+
+impl Document {
+    pub fn parse(content: &str) -> Result<Document, String> {
+        let (result, mut builder) = build(content, DocumentBuilder::default());
+        result?;
+        builder.strings.shrink_to_fit();
+        Ok(builder.finish())
+    }
+
+    // Parses another input into buffers of this document, which keep their capacity, so
+    // a document reused for many inputs stops allocating once it has seen the largest one.
+    // On an error the document is left as `null`
+    pub fn reparse(&mut self, content: &str) -> Result<(), String> {
+        let (result, mut builder) = build(content, DocumentBuilder::reusing(self));
+        if result.is_err() {
+            builder.clear();
+            builder.push_value(Value::Null);
+        }
+        *self = builder.finish();
+        result
+    }
+
+    pub fn root(&self) -> NodeRef<'_> {
+        NodeRef { doc: self, idx: self.root }
+    }
+
+    pub fn to_node(&self) -> Node {
+        self.root().to_node()
+    }
+
+    // Bytes held by the document buffers
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Document>() +
+            self.strings.capacity() +
+            self.values.capacity() * mem::size_of::<Value>() +
+            self.elements.capacity() * mem::size_of::<u32>() +
+            self.entries.capacity() * mem::size_of::<Entry>() +
+            self.sorted.capacity() * mem::size_of::<u32>()
+    }
+
+    fn str_at(&self, start: u32, len: u32) -> &str {
+        &self.strings[start as usize..(start + len) as usize]
+    }
+}
+
+// Read-only view of a value inside a `Document`, offers the same getters as `Node`
+#[derive(Copy, Clone, Debug)]
+pub struct NodeRef<'d> {
+    doc: &'d Document,
+    idx: u32
+}
+
+// Equal when `to_node()` would give equal nodes, walking both documents side by side
+impl<'d> PartialEq for NodeRef<'d> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.value(), other.value()) {
+            (Value::String { .. }, Value::String { .. }) => self.as_string() == other.as_string(),
+            (Value::Array { len, .. }, Value::Array { len: other_len, .. }) =>
+                len == other_len && self.elements().zip(other.elements()).all(|(a, b)| a == b),
+            (Value::Object { len, .. }, Value::Object { len: other_len, .. }) =>
+                len == other_len && self.sorted_entries().zip(other.sorted_entries()).all(|(a, b)| a == b),
+            (value, other_value) => value == other_value
+        }
+    }
+}
+
+impl<'d> NodeRef<'d> {
+    fn value(&self) -> Value {
+        self.doc.values[self.idx as usize]
+    }
+
+    fn at_index(&self, idx: u32) -> NodeRef<'d> {
+        NodeRef { doc: self.doc, idx }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.value() == Value::Null
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.value() {
+            Value::Boolean(b) => Some(b),
+            _ => None
+        }
+    }
+
+    pub fn is_bool(&self) -> bool {
+        self.as_bool().is_some()
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self.value() {
+            Value::Number(n) => Some(n),
+            _ => None
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.as_number().is_some()
+    }
+
+    pub fn as_string(&self) -> Option<&'d str> {
+        match self.value() {
+            Value::String { start, len } => Some(self.doc.str_at(start, len)),
+            _ => None
+        }
+    }
+
+    pub fn is_string(&self) -> bool {
+        self.as_string().is_some()
+    }
+
+    pub fn to_string(&self) -> Option<String> {
+        match self.value() {
+            Value::Null => Some("null".to_string()),
+            Value::Boolean(b) => Some(b.to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self.value(), Value::Array { .. })
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(self.value(), Value::Object { .. })
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self.value() {
+            Value::Null => "null",
+            Value::Boolean(_) => "bool",
+            Value::Number(_) => "number",
+            Value::String { .. } => "string",
+            Value::Array { .. } => "array",
+            Value::Object { .. } => "object"
+        }
+    }
+
+    pub fn try_len(&self) -> Option<usize> {
+        match self.value() {
+            Value::Array { len, .. } => Some(len as usize),
+            Value::Object { len, .. } => Some(len as usize),
+            _ => None
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.try_len().expect("it appears that node is not array or dictionary so it has no len!")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.try_len() == Some(0)
+    }
+
+    // Array elements; empty for anything else
+    pub fn elements(&self) -> impl Iterator<Item = NodeRef<'d>> + 'd {
+        let (first, len) = match self.value() {
+            Value::Array { first, len } => (first as usize, len as usize),
+            _ => (0, 0)
+        };
+        let doc = self.doc;
+        doc.elements[first..first + len].iter().map(move |&idx| NodeRef { doc, idx })
+    }
+
+    // Object members in the order they were written, without earlier duplicates of a key;
+    // empty for anything else
+    pub fn entries(&self) -> impl Iterator<Item = (&'d str, NodeRef<'d>)> + 'd {
+        let (first, len) = match self.value() {
+            Value::Object { first, len } => (first as usize, len as usize),
+            _ => (0, 0)
+        };
+        let doc = self.doc;
+        doc.entries[first..first + len].iter().map(move |entry|
+            (doc.str_at(entry.key_start, entry.key_len), NodeRef { doc, idx: entry.value })
+        )
+    }
+
+    pub fn get_element_at(&self, idx: usize) -> Result<NodeRef<'d>, ErrorCause> {
+        match self.value() {
+            Value::Array { first, len } => if idx < len as usize {
+                Ok(self.at_index(self.doc.elements[first as usize + idx]))
+            } else {
+                Err(IndexOutOfBound(idx))
+            },
+            _ => Err(NodeIsNotArray)
+        }
+    }
+
+    fn sorted_entries(&self) -> impl Iterator<Item = (&'d str, NodeRef<'d>)> + 'd {
+        let (first, len) = match self.value() {
+            Value::Object { first, len } => (first as usize, len as usize),
+            _ => (0, 0)
+        };
+        let doc = self.doc;
+        doc.sorted[first..first + len].iter().map(move |&idx| {
+            let entry = &doc.entries[idx as usize];
+            (entry_key(&doc.strings, entry), NodeRef { doc, idx: entry.value })
+        })
+    }
+
+    pub fn get(&self, key: &str) -> Result<NodeRef<'d>, ErrorCause> {
+        let (first, len) = match self.value() {
+            Value::Object { first, len } => (first as usize, len as usize),
+            _ => return Err(NodeIsNotADictionary)
+        };
+        let doc = self.doc;
+        let sorted = &doc.sorted[first..first + len];
+        match sorted.binary_search_by(|&idx| entry_key(&doc.strings, &doc.entries[idx as usize]).cmp(key)) {
+            Ok(pos) => Ok(self.at_index(doc.entries[sorted[pos] as usize].value)),
+            Err(_) => Err(FieldNotExist(key.to_string()))
+        }
+    }
+
+    node_getters! {
+        child: NodeRef<'d>, element: get_element_at, string: &'d str, as_string: as_string
+    }
+
+    integer_getters! {
+        as_i8, get_i8: i8;
+        as_i16, get_i16: i16;
+        as_i32, get_i32: i32;
+        as_i64, get_i64: i64;
+        as_isize, get_isize: isize;
+        as_u8, get_u8: u8;
+        as_u16, get_u16: u16;
+        as_u32, get_u32: u32;
+        as_u64, get_u64: u64;
+        as_usize, get_usize: usize;
+    }
+
+    pub fn to_node(&self) -> Node {
+        match self.value() {
+            Value::Null => Node::Null,
+            Value::Boolean(b) => Node::Boolean(b),
+            Value::Number(n) => Node::Number(n),
+            Value::String { start, len } => Node::String(self.doc.str_at(start, len).into()),
+            Value::Array { .. } => Node::Array(self.elements().map(|e| e.to_node()).collect()),
+            Value::Object { len, .. } => {
+                let mut dict = HashMap::with_capacity(len as usize);
+                for (k, v) in self.entries() {
+                    dict.insert(k.into(), v.to_node());
+                }
+                Node::Object(dict)
+            }
+        }
+    }
+}
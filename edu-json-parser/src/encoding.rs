@@ -0,0 +1,146 @@
+use std::borrow::Cow;
+use std::char;
+use std::str;
+use crate::{Node, parse_json};
+use crate::errors::ParseError;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be
+}
+
+// What to do with bytes which are not valid in the input encoding
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum InvalidInput {
+    Fail,
+    // replace them with U+FFFD
+    Replace
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct BytesOptions {
+    // look for UTF-16 and UTF-32 as well, otherwise the input is UTF-8
+    pub detect_encoding: bool,
+    pub invalid: InvalidInput
+}
+
+impl Default for BytesOptions {
+    fn default() -> Self {
+        BytesOptions {
+            detect_encoding: false,
+            invalid: InvalidInput::Fail
+        }
+    }
+}
+
+// Encoding of the input and a length of its BOM. Without a BOM, the encoding is guessed
+// from zero bytes around the first char, which is always ASCII in JSON (RFC 8259, 8.1)
+pub fn detect_encoding(bytes: &[u8]) -> (Encoding, usize) {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => (Encoding::Utf8, 3),
+        [0x00, 0x00, 0xFE, 0xFF, ..] => (Encoding::Utf32Be, 4),
+        [0xFF, 0xFE, 0x00, 0x00, ..] => (Encoding::Utf32Le, 4),
+        [0xFE, 0xFF, ..] => (Encoding::Utf16Be, 2),
+        [0xFF, 0xFE, ..] => (Encoding::Utf16Le, 2),
+        [0x00, 0x00, 0x00, _, ..] => (Encoding::Utf32Be, 0),
+        [_, 0x00, 0x00, 0x00, ..] => (Encoding::Utf32Le, 0),
+        [0x00, _, ..] => (Encoding::Utf16Be, 0),
+        [_, 0x00, ..] => (Encoding::Utf16Le, 0),
+        _ => (Encoding::Utf8, 0)
+    }
+}
+
+fn decode_utf8(bytes: &[u8], start: usize, invalid: InvalidInput) -> Result<Cow<'_, str>, ParseError> {
+    let content = &bytes[start..];
+    match str::from_utf8(content) {
+        Ok(s) => Ok(Cow::Borrowed(s)),
+        Err(_) if invalid == InvalidInput::Replace => Ok(String::from_utf8_lossy(content)),
+        Err(e) => Err(ParseError::new(start + e.valid_up_to(), "invalid UTF-8"))
+    }
+}
+
+// `units` are code units together with their byte offsets
+fn decode_utf16<I>(units: I, len: usize, invalid: InvalidInput) -> Result<String, ParseError>
+    where I: Iterator<Item = (usize, u16)>
+{
+    let mut s = String::with_capacity(len);
+    let mut units = units.peekable();
+    while let Some((offset, unit)) = units.next() {
+        let c = match unit {
+            0xD800..=0xDBFF => match units.peek() {
+                Some(&(_, low)) if (0xDC00..=0xDFFF).contains(&low) => {
+                    units.next();
+                    char::from_u32(0x10000 + ((unit as u32 - 0xD800) << 10) + (low as u32 - 0xDC00))
+                },
+                _ => None
+            },
+            _ => char::from_u32(unit as u32)
+        };
+        match (c, invalid) {
+            (Some(c), _) => s.push(c),
+            (None, InvalidInput::Replace) => s.push(char::REPLACEMENT_CHARACTER),
+            (None, InvalidInput::Fail) => return Err(ParseError::new(offset, "invalid UTF-16"))
+        }
+    }
+    Ok(s)
+}
+
+fn decode_utf32<I>(units: I, len: usize, invalid: InvalidInput) -> Result<String, ParseError>
+    where I: Iterator<Item = (usize, u32)>
+{
+    let mut s = String::with_capacity(len);
+    for (offset, unit) in units {
+        match (char::from_u32(unit), invalid) {
+            (Some(c), _) => s.push(c),
+            (None, InvalidInput::Replace) => s.push(char::REPLACEMENT_CHARACTER),
+            (None, InvalidInput::Fail) => return Err(ParseError::new(offset, "invalid UTF-32"))
+        }
+    }
+    Ok(s)
+}
+
+// Turns raw input into text. A UTF-8 BOM is always dropped, other encodings
+// are only looked for when `detect_encoding` is set. Error offsets are in bytes of the input
+pub fn decode_json_bytes(bytes: &[u8], options: BytesOptions) -> Result<Cow<'_, str>, ParseError> {
+    let (encoding, bom) = match detect_encoding(bytes) {
+        (Encoding::Utf8, bom) => (Encoding::Utf8, bom),
+        _ if !options.detect_encoding => (Encoding::Utf8, 0),
+        detected => detected
+    };
+    let invalid = options.invalid;
+    let body = &bytes[bom..];
+    let unit = match encoding {
+        Encoding::Utf8 => return decode_utf8(bytes, bom, invalid),
+        Encoding::Utf16Le | Encoding::Utf16Be => 2,
+        Encoding::Utf32Le | Encoding::Utf32Be => 4
+    };
+    let tail = body.len() % unit;
+    if tail > 0 && invalid == InvalidInput::Fail {
+        return Err(ParseError::new(bytes.len() - tail, "truncated code unit"));
+    }
+    let chunks = body.chunks_exact(unit).enumerate().map(|(i, c)| (bom + i * unit, c));
+    let mut text = match encoding {
+        Encoding::Utf16Le => decode_utf16(chunks.map(|(o, c)| (o, u16::from_le_bytes([c[0], c[1]]))), body.len(), invalid),
+        Encoding::Utf16Be => decode_utf16(chunks.map(|(o, c)| (o, u16::from_be_bytes([c[0], c[1]]))), body.len(), invalid),
+        Encoding::Utf32Le => decode_utf32(chunks.map(|(o, c)| (o, u32::from_le_bytes([c[0], c[1], c[2], c[3]]))), body.len(), invalid),
+        _ => decode_utf32(chunks.map(|(o, c)| (o, u32::from_be_bytes([c[0], c[1], c[2], c[3]]))), body.len(), invalid)
+    }?;
+    if tail > 0 {
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+    Ok(Cow::Owned(text))
+}
+
+// UTF-8 input with an optional BOM; invalid UTF-8 is an error
+pub fn parse_json_bytes(bytes: &[u8]) -> Result<Node, String> {
+    parse_json_bytes_with(bytes, BytesOptions::default())
+}
+
+pub fn parse_json_bytes_with(bytes: &[u8], options: BytesOptions) -> Result<Node, String> {
+    let text = decode_json_bytes(bytes, options).map_err(|e| e.to_string())?;
+    parse_json(&text)
+}
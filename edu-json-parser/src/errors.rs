@@ -1,10 +1,21 @@
+use std::fmt::{Display, Formatter};
+
 #[derive(PartialEq, Clone, Debug)]
 pub enum ErrorCause {
     FieldNotExist(String),
-    WrongTypeRequested(String, &'static str),
+    // a field, the type it was asked for and the type it has
+    WrongTypeRequested(String, &'static str, &'static str),
     IndexOutOfBound(usize),
     NodeIsNotArray,
-    NodeIsNotADictionary
+    NodeIsNotADictionary,
+    // a length of a scalar
+    NodeHasNoLength,
+    InvalidPointer(String),
+    MalformedJson(String),
+    NotAnInteger(String, f64, &'static str),
+    NumberOutOfRange(String, f64, &'static str),
+    // beyond 2^53, where the parsed value may have been rounded
+    InexactNumber(String, f64, &'static str)
 }
 
 impl ToString for ErrorCause {
@@ -13,9 +24,9 @@ impl ToString for ErrorCause {
             ErrorCause::FieldNotExist(field_name) => format!(
                 "Field with a name '{}' does not exist", field_name
             ),
-            ErrorCause::WrongTypeRequested(field_name, type_name) => format!(
-                "Trying to look at field '{}' like it was of a type `{}`, but found something other instead",
-                field_name, type_name
+            ErrorCause::WrongTypeRequested(field_name, type_name, found) => format!(
+                "Trying to look at field '{}' like it was of a type `{}`, but found `{}` instead",
+                field_name, type_name, found
             ),
             ErrorCause::IndexOutOfBound(idx) => format!(
                 "Index {} is out of bounds", idx
@@ -24,6 +35,60 @@ impl ToString for ErrorCause {
                 "Trying to work with a node like it was an array, but it didn't".to_string(),
             ErrorCause::NodeIsNotADictionary =>
                 "Trying to work with a node like it was a dictionary, but it didn't".to_string(),
+            ErrorCause::NodeHasNoLength =>
+                "Trying to get a length of a node which is neither an array nor a dictionary".to_string(),
+            ErrorCause::InvalidPointer(pointer) => format!(
+                "'{}' is not a valid JSON pointer", pointer
+            ),
+            ErrorCause::MalformedJson(error_text) => format!(
+                "Malformed JSON: {}", error_text
+            ),
+            ErrorCause::NotAnInteger(field_name, value, type_name) => format!(
+                "Field '{}' holds {}, which is not an integer, so it can't be read as `{}`",
+                field_name, value, type_name
+            ),
+            ErrorCause::NumberOutOfRange(field_name, value, type_name) => format!(
+                "Field '{}' holds {}, which is out of range of `{}`",
+                field_name, value, type_name
+            ),
+            ErrorCause::InexactNumber(field_name, value, type_name) => format!(
+                "Field '{}' holds {}, which may have been rounded, so it can't be read as `{}` exactly",
+                field_name, value, type_name
+            ),
         }
     }
-}
\ No newline at end of file
+}
+
+// Error of a parser which knows where exactly it has stopped
+#[derive(PartialEq, Clone, Debug)]
+pub struct ParseError {
+    pub offset: usize,
+    pub message: String
+}
+
+impl ParseError {
+    pub(crate) fn new(offset: usize, message: &str) -> Self {
+        ParseError { offset, message: message.to_string() }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.offset)
+    }
+}
+
+// Panic of the `expect_*` accessors: the call with its key or index, the type of the node
+// and why it failed
+pub(crate) fn accessor_panic(call: String, kind: &str, cause: ErrorCause) -> ! {
+    panic!("{} on a node of type {}: {}", call, kind, cause.to_string())
+}
+
+// Panic of `expect_pointer`: the whole pointer, the part of it which has been found,
+// the type of the node there and why it could not go further
+pub(crate) fn pointer_panic(pointer: &str, found: &str, kind: &str, cause: ErrorCause) -> ! {
+    panic!(
+        "expect_pointer({:?}) stopped at '{}' on a node of type {}: {}",
+        pointer, found, kind, cause.to_string()
+    )
+}
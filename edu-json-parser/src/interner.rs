@@ -0,0 +1,60 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use smol_str::SmolStr;
+use crate::Node;
+use crate::sink::{SinkState, NodeBuilder, parse_into};
+
+// Keeps one copy of every object key it has seen. Keys longer than 22 bytes are
+// heap allocated by `SmolStr`, and clones of an interned key share that allocation.
+// Shorter keys are stored inline anyway, so they cost nothing extra either way
+#[derive(Default, Clone, Debug)]
+pub struct KeyInterner {
+    keys: HashSet<SmolStr>
+}
+
+impl KeyInterner {
+    pub fn new() -> Self {
+        KeyInterner::default()
+    }
+
+    pub fn intern(&mut self, key: &str) -> SmolStr {
+        if let Some(interned) = self.keys.get(key) {
+            return interned.clone();
+        }
+        let interned = SmolStr::new(key);
+        self.keys.insert(interned.clone());
+        interned
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    // Bytes of key text living on the heap, shared by every node built with this interner
+    pub fn heap_bytes(&self) -> usize {
+        self.keys.iter()
+            .filter(|key| key.is_heap_allocated())
+            .map(|key| key.len())
+            .sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+}
+
+// The same as `parse_json`, but equal object keys share one allocation
+pub fn parse_json_interned(content: &str) -> Result<Node, String> {
+    parse_json_with_interner(content, &mut KeyInterner::new())
+}
+
+// Pass the same interner for a batch of similar documents, so they share key storage too
+pub fn parse_json_with_interner(content: &str, interner: &mut KeyInterner) -> Result<Node, String> {
+    let state = RefCell::new(SinkState::new(NodeBuilder::new(Some(interner)), String::new()));
+    parse_into(content, &state)?;
+    Ok(state.into_inner().sink.finish())
+}
@@ -0,0 +1,346 @@
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::ops::Index;
+use crate::{Node, parse_json, is_json_whitespace};
+use crate::errors::ErrorCause;
+use crate::errors::ErrorCause::*;
+use crate::two_stage::{parse_atom, decode_string};
+
+// A value which is only a slice of the input until somebody looks inside. The first access
+// to children of an array or an object splits it into children, skimming over their
+// contents, and that split is kept. Scalars are parsed on every access, which is cheap.
+// Note that only brackets and strings are checked in parts nobody has looked at, a malformed
+// scalar found there later is of the "invalid" kind
+#[derive(Clone, Debug)]
+pub struct LazyNode<'a> {
+    raw: &'a str,
+    children: OnceCell<Result<Children<'a>, String>>,
+    // what `Index` gives for missing values. `OnceCell` makes `LazyNode` invariant over 'a
+    // and not `Sync`, so there can't be one static null like `Node` has
+    null: OnceCell<Box<LazyNode<'a>>>
+}
+
+#[derive(Clone, Debug)]
+enum Children<'a> {
+    Array(Vec<LazyNode<'a>>),
+    Object(Vec<(Cow<'a, str>, LazyNode<'a>)>),
+    None
+}
+
+pub(crate) fn skip_whitespace(s: &str, pos: usize) -> usize {
+    s.len() - s[pos..].trim_start_matches(is_json_whitespace).len()
+}
+
+// Offset right after a string which starts at `pos`
+fn skim_string(s: &str, pos: usize) -> Result<usize, String> {
+    let bytes = s.as_bytes();
+    let mut i = pos + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => return Ok(i + 1),
+            b'\\' => i += 2,
+            _ => i += 1
+        }
+    }
+    Err(format!("unterminated string at byte {}", pos))
+}
+
+// Offset right after a value which starts at `pos`. Nested values are only checked
+// for balanced brackets; scalars run up to the next delimiter
+pub(crate) fn skim_value(s: &str, pos: usize) -> Result<usize, String> {
+    let bytes = s.as_bytes();
+    match bytes.get(pos) {
+        None => Err("expected a value, found the end of input".to_string()),
+        Some(b'"') => skim_string(s, pos),
+        Some(b'[') | Some(b'{') => {
+            let mut expected = Vec::new();
+            let mut i = pos;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = skim_string(s, i)?;
+                        continue;
+                    },
+                    b'[' => expected.push(b']'),
+                    b'{' => expected.push(b'}'),
+                    b @ b']' | b @ b'}' => {
+                        if expected.pop() != Some(b) {
+                            return Err(format!("unexpected '{}' at byte {}", b as char, i));
+                        }
+                        if expected.is_empty() {
+                            return Ok(i + 1);
+                        }
+                    },
+                    _ => {}
+                }
+                i += 1;
+            }
+            Err(format!("unbalanced brackets at byte {}", pos))
+        },
+        Some(_) => {
+            let len = s[pos..]
+                .find(|c: char| c == ',' || c == ']' || c == '}' || c == ':' || is_json_whitespace(c))
+                .unwrap_or(s.len() - pos);
+            if len == 0 {
+                Err(format!("expected a value at byte {}", pos))
+            } else {
+                Ok(pos + len)
+            }
+        }
+    }
+}
+
+fn expect(s: &str, pos: usize, c: u8) -> Result<usize, String> {
+    if s.as_bytes().get(pos) == Some(&c) {
+        Ok(pos + 1)
+    } else {
+        Err(format!("expected '{}' at byte {}", c as char, pos))
+    }
+}
+
+// Splits `raw` (which starts with `open`) into items, `item` returns the next offset
+fn split<T, F>(raw: &str, close: u8, mut item: F) -> Result<Vec<T>, String>
+    where F: FnMut(usize) -> Result<(T, usize), String>
+{
+    let mut items = Vec::new();
+    let mut pos = skip_whitespace(raw, 1);
+    if raw.as_bytes().get(pos) == Some(&close) {
+        pos += 1;
+    } else {
+        loop {
+            let (value, next) = item(pos)?;
+            items.push(value);
+            pos = skip_whitespace(raw, next);
+            match raw.as_bytes().get(pos) {
+                Some(b',') => pos += 1,
+                Some(&b) if b == close => {
+                    pos += 1;
+                    break;
+                },
+                _ => return Err(format!("expected ',' or '{}' at byte {}", close as char, pos))
+            }
+        }
+    }
+    if pos == raw.len() {
+        Ok(items)
+    } else {
+        Err(format!("unexpected characters at byte {}", pos))
+    }
+}
+
+fn child<'a>(raw: &'a str, pos: usize) -> Result<(LazyNode<'a>, usize), String> {
+    let start = skip_whitespace(raw, pos);
+    let end = skim_value(raw, start)?;
+    Ok((LazyNode::new(&raw[start..end]), end))
+}
+
+pub fn parse_json_lazy(content: &str) -> Result<LazyNode<'_>, String> {
+    let (node, end) = child(content, 0)?;
+    if skip_whitespace(content, end) != content.len() {
+        Err(format!("unexpected characters at byte {}", end))
+    } else if node.kind() == "invalid" {
+        // nobody else would look at a scalar at the top
+        Err(format!("invalid value at byte {}", skip_whitespace(content, 0)))
+    } else {
+        Ok(node)
+    }
+}
+
+impl<'a> LazyNode<'a> {
+    fn new(raw: &'a str) -> Self {
+        LazyNode { raw, children: OnceCell::new(), null: OnceCell::new() }
+    }
+
+    // The text of this value, as it is in the input
+    pub fn raw(&self) -> &'a str {
+        self.raw
+    }
+
+    // Parses the whole value, the only way to be sure it is well formed
+    pub fn to_node(&self) -> Result<Node, String> {
+        parse_json(self.raw)
+    }
+
+    fn children(&self) -> Result<&Children<'a>, ErrorCause> {
+        let raw = self.raw;
+        let children = self.children.get_or_init(|| match raw.as_bytes()[0] {
+            b'[' => split(raw, b']', |pos| child(raw, pos)).map(Children::Array),
+            b'{' => split(raw, b'}', |pos| {
+                let key_start = skip_whitespace(raw, pos);
+                let key_end = match raw.as_bytes().get(key_start) {
+                    Some(b'"') => skim_string(raw, key_start)?,
+                    _ => return Err(format!("expected a key at byte {}", key_start))
+                };
+                let key = decode_string(&raw[key_start + 1..key_end - 1])
+                    .ok_or_else(|| format!("invalid key at byte {}", key_start))?;
+                let colon = expect(raw, skip_whitespace(raw, key_end), b':')?;
+                let (value, end) = child(raw, colon)?;
+                Ok(((key, value), end))
+            }).map(Children::Object),
+            _ => Ok(Children::None)
+        });
+        children.as_ref().map_err(|e| MalformedJson(e.clone()))
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.raw == "null"
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.raw {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None
+        }
+    }
+
+    pub fn is_bool(&self) -> bool {
+        self.as_bool().is_some()
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match parse_atom(self.raw) {
+            Some(Node::Number(n)) => Some(n),
+            _ => None
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.as_number().is_some()
+    }
+
+    // Borrowed from the input unless the string has escapes
+    pub fn as_string(&self) -> Option<Cow<'a, str>> {
+        if self.raw.len() < 2 || !self.raw.starts_with('"') {
+            return None;
+        }
+        decode_string(&self.raw[1..self.raw.len() - 1])
+    }
+
+    pub fn is_string(&self) -> bool {
+        self.as_string().is_some()
+    }
+
+    // Numbers are spelled the way `Node::to_string` does, not the way they are in `raw()`
+    pub fn to_string(&self) -> Option<String> {
+        match parse_atom(self.raw)? {
+            Node::Null => Some("null".to_string()),
+            Node::Boolean(b) => Some(b.to_string()),
+            Node::Number(n) => Some(n.to_string()),
+            _ => None
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[LazyNode<'a>]> {
+        match self.children() {
+            Ok(Children::Array(items)) => Some(items),
+            _ => None
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.raw.starts_with('[')
+    }
+
+    pub fn is_object(&self) -> bool {
+        self.raw.starts_with('{')
+    }
+
+    // "invalid" for a malformed scalar, containers are only told apart by their first byte
+    pub fn kind(&self) -> &'static str {
+        match self.raw.as_bytes().first() {
+            Some(b'[') => "array",
+            Some(b'{') => "object",
+            Some(b'"') if self.is_string() => "string",
+            _ => parse_atom(self.raw).map_or("invalid", |node| node.kind())
+        }
+    }
+
+    // None for scalars and for containers which turn out to be malformed
+    pub fn try_len(&self) -> Option<usize> {
+        match self.children() {
+            Ok(Children::Array(items)) => Some(items.len()),
+            Ok(Children::Object(entries)) => Some(entries.len()),
+            _ => None
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.try_len().expect("it appears that node is not array or dictionary so it has no len!")
+    }
+
+    fn null(&self) -> &LazyNode<'a> {
+        self.null.get_or_init(|| Box::new(LazyNode::new("null")))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.try_len() == Some(0)
+    }
+
+    // Array elements; empty for anything else
+    pub fn elements(&self) -> impl Iterator<Item = &LazyNode<'a>> {
+        self.as_array().unwrap_or(&[]).iter()
+    }
+
+    // Object members in the order they were written; empty for anything else
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &LazyNode<'a>)> {
+        let entries = match self.children() {
+            Ok(Children::Object(entries)) => &entries[..],
+            _ => &[]
+        };
+        entries.iter().map(|(k, v)| (k.as_ref(), v))
+    }
+
+    pub fn get_element_at(&self, idx: usize) -> Result<&LazyNode<'a>, ErrorCause> {
+        match self.children()? {
+            Children::Array(items) => items.get(idx).ok_or(IndexOutOfBound(idx)),
+            _ => Err(NodeIsNotArray)
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Result<&LazyNode<'a>, ErrorCause> {
+        match self.children()? {
+            // members are kept as they were written, so duplicates are looked up from the end
+            Children::Object(entries) => entries.iter()
+                .rev()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v)
+                .ok_or_else(|| FieldNotExist(key.to_string())),
+            _ => Err(NodeIsNotADictionary)
+        }
+    }
+
+    node_getters! {
+        child: &LazyNode<'a>, element: get_element_at, string: Cow<'a, str>, as_string: as_string
+    }
+
+    integer_getters! {
+        as_i8, get_i8: i8;
+        as_i16, get_i16: i16;
+        as_i32, get_i32: i32;
+        as_i64, get_i64: i64;
+        as_isize, get_isize: isize;
+        as_u8, get_u8: u8;
+        as_u16, get_u16: u16;
+        as_u32, get_u32: u32;
+        as_u64, get_u64: u64;
+        as_usize, get_usize: usize;
+    }
+}
+
+// missing values are the null every node keeps for that
+impl<'a> Index<&str> for LazyNode<'a>
+{
+    type Output = LazyNode<'a>;
+    fn index(&self, key: &str) -> &Self::Output {
+        self.get(key).unwrap_or_else(|_| self.null())
+    }
+}
+
+impl<'a> Index<usize> for LazyNode<'a>
+{
+    type Output = LazyNode<'a>;
+    fn index(&self, key: usize) -> &Self::Output {
+        self.get_element_at(key).unwrap_or_else(|_| self.null())
+    }
+}
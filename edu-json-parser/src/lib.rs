@@ -2,21 +2,163 @@
 extern crate compre_combinee;
 extern crate combine;
 
+// Recursion point of the grammars: `$make` gives a parser for a value `$depth` levels
+// deep, it is only called when the parser runs, so the grammar types stay finite
+macro_rules! nested {
+    ($depth:expr, $make:expr) => {{
+        let depth: usize = $depth;
+        combine::parser(move |input: &mut &str| {
+            if depth > crate::MAX_DEPTH {
+                combine::unexpected_any("nesting is too deep").parse_stream(input).into_result()
+            } else {
+                $make.parse_stream(input).into_result()
+            }
+        })
+    }}
+}
+
+// `as_i64`, `get_i64` and the others for every node type which has `as_number` and `get`
+macro_rules! integer_getters {
+    ($($as_name:ident, $get_name:ident: $ty:ident;)*) => {
+        $(
+            pub fn $as_name(&self) -> Option<$ty> {
+                self.as_number().and_then(|n| crate::details::integer("", n, stringify!($ty)).ok())
+            }
+
+            pub fn $get_name(&self, key: &str) -> Result<$ty, crate::ErrorCause> {
+                let node = self.get(key)?;
+                let n = node.as_number()
+                    .ok_or_else(|| crate::ErrorCause::WrongTypeRequested(key.to_string(), stringify!($ty), node.kind()))?;
+                crate::details::integer(key, n, stringify!($ty))
+            }
+        )*
+    }
+}
+
+// The panicking `expect_*` accessors and the typed getters for every node type which has `kind`,
+// `try_len`, `is_array`, `get` and the `as_*` methods. `$child` is what `get` gives, `$element`
+// is a method giving an element of an array the same way, and `$as_string` gives `$string`
+macro_rules! node_getters {
+    (child: $child:ty, element: $element:ident, string: $string:ty, as_string: $as_string:ident) => {
+        // Unlike `len()` and `Index`, the message tells what was asked for and what the node is
+        pub fn expect_len(&self) -> usize {
+            match self.try_len() {
+                Some(len) => len,
+                None => crate::errors::accessor_panic("expect_len()".to_string(), self.kind(), crate::ErrorCause::NodeHasNoLength)
+            }
+        }
+
+        pub fn expect_key(&self, key: &str) -> $child {
+            match self.get(key) {
+                Ok(node) => node,
+                Err(cause) => crate::errors::accessor_panic(format!("expect_key({:?})", key), self.kind(), cause)
+            }
+        }
+
+        pub fn expect_at(&self, idx: usize) -> $child {
+            match self.$element(idx) {
+                Ok(node) => node,
+                Err(cause) => crate::errors::accessor_panic(format!("expect_at({})", idx), self.kind(), cause)
+            }
+        }
+
+        // `expect_key` and `expect_at` along a JSON Pointer like `/sizes/2`, panics with the
+        // part of the pointer which has been found
+        pub fn expect_pointer(&self, pointer: &str) -> $child {
+            // `self` itself for references, a copy of `self` for views
+            let mut node = <$child as Clone>::clone(&self);
+            for (token, parent) in crate::pointer::expect_steps(pointer, self.kind()) {
+                let child = if node.is_array() {
+                    crate::pointer::parse_index(&token).and_then(|idx| node.$element(idx))
+                } else {
+                    node.get(&token)
+                };
+                node = child.unwrap_or_else(|cause| crate::errors::pointer_panic(pointer, &parent, node.kind(), cause));
+            }
+            node
+        }
+
+        pub fn get_string(&self, key: &str) -> Result<$string, crate::ErrorCause> {
+            let node = self.get(key)?;
+            node.$as_string()
+                .ok_or_else(|| crate::ErrorCause::WrongTypeRequested(key.to_string(), "string", node.kind()))
+        }
+
+        pub fn get_as_string(&self, key: &str) -> Result<String, crate::ErrorCause> {
+            let node = self.get(key)?;
+            node.to_string()
+                .ok_or_else(|| crate::ErrorCause::WrongTypeRequested(key.to_string(), "as string", node.kind()))
+        }
+
+        pub fn get_number(&self, key: &str) -> Result<f64, crate::ErrorCause> {
+            let node = self.get(key)?;
+            node.as_number()
+                .ok_or_else(|| crate::ErrorCause::WrongTypeRequested(key.to_string(), "number", node.kind()))
+        }
+
+        pub fn get_bool(&self, key: &str) -> Result<bool, crate::ErrorCause> {
+            let node = self.get(key)?;
+            node.as_bool()
+                .ok_or_else(|| crate::ErrorCause::WrongTypeRequested(key.to_string(), "bool", node.kind()))
+        }
+    }
+}
+
 mod errors;
 mod details;
 mod traits;
+mod decode;
 mod stop_watch;
+mod stream;
+mod serializer;
+mod pointer;
+mod cst;
+mod span;
+mod borrowed;
+mod sink;
+mod document;
+mod interner;
+mod tape;
+mod structural;
+mod two_stage;
+mod lazy;
+mod parallel;
+mod validate;
+mod encoding;
+mod reusable;
+mod tolerant;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 use std::collections::{HashMap};
-use combine::{parser, eof, satisfy, choice, attempt};
-use combine::parser::range::{take_while1};
+use combine::{eof, satisfy, choice, attempt, value, unexpected_any};
+use combine::parser::range::{take_while1, recognize};
 use combine::parser::char::*;
 use combine::{Parser, many, optional, skip_many, sep_by, between};
 
-pub use crate::errors::ErrorCause;
+pub use crate::errors::{ErrorCause, ParseError};
 pub use crate::details::Node;
 pub use crate::traits::*;
-use std::{f64, mem, str};
+pub use crate::decode::DecodeError;
+#[cfg(feature = "derive")]
+pub use edu_json_parser_derive::Parsable;
+pub use crate::stream::{JsonStream, Framing, parse_json_stream, RECORD_SEPARATOR};
+pub use crate::cst::{CstDocument, CstValue, CstArray, CstObject};
+pub use crate::pointer::escape_pointer_token;
+pub use crate::borrowed::{BorrowedNode, parse_json_borrowed};
+pub use crate::document::{Document, NodeRef};
+pub use crate::structural::{StructuralIndex, Kernel};
+pub use crate::two_stage::{parse_json_simd, parse_json_simd_with, parse_json_simd_bytes};
+pub use crate::lazy::{LazyNode, parse_json_lazy};
+pub use crate::parallel::{parse_json_parallel, parse_json_parallel_with, parse_json_lines_parallel, parse_json_lines_parallel_with};
+pub use crate::validate::validate_json;
+pub use crate::encoding::{Encoding, InvalidInput, BytesOptions, detect_encoding, decode_json_bytes, parse_json_bytes, parse_json_bytes_with};
+pub use crate::reusable::JsonParser;
+pub use crate::tolerant::{Diagnostic, parse_json_tolerant};
+pub use crate::tape::{Tape, TapeCursor, TapeElements, TapeEntries};
+pub use crate::interner::{KeyInterner, parse_json_interned, parse_json_with_interner};
+pub use crate::span::{parse_json_spanned, Position, Span, NodeSpans, SpanTable, SpannedNode, LocatedError};
+use std::{mem, str};
 use std::convert::TryFrom;
 use smol_str::SmolStr;
 
@@ -35,23 +177,43 @@ fn parse_hex<'a>() -> impl Parser<&'a str, Output = u32> {
     )
 }
 
-fn unicode_char<'a>() -> impl Parser<&'a str, Output = Option<char>> {
+fn hex_escape<'a>() -> impl Parser<&'a str, Output = u32> {
     c_hx_do!{
         __ <- string(r#"\u"#),
         d3 <- parse_hex(),
         d2 <- parse_hex(),
         d1 <- parse_hex(),
         d0 <- parse_hex();
-        {
-            let unicode = d0 +
-                0x10 * d1 +
-                0x100 * d2 +
-                0x1000 * d3;
-            char::try_from(unicode).ok()
-        }
+        d0 +
+            0x10 * d1 +
+            0x100 * d2 +
+            0x1000 * d3
     }
 }
 
+fn low_surrogate<'a>() -> impl Parser<&'a str, Output = u32> {
+    attempt(hex_escape().then(|unit| {
+        if (0xDC00..0xE000).contains(&unit) {
+            value(unit).left()
+        } else {
+            unexpected_any("a low surrogate").right()
+        }
+    }))
+}
+
+// A high surrogate makes a char together with a low one right after it, lone surrogates are dropped
+fn unicode_char<'a>() -> impl Parser<&'a str, Output = Option<char>> {
+    hex_escape().then(|unit| {
+        if (0xD800..0xDC00).contains(&unit) {
+            optional(low_surrogate())
+                .map(move |low| low.and_then(|low| char::from_u32(0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00))))
+                .left()
+        } else {
+            value(char::try_from(unit).ok()).right()
+        }
+    })
+}
+
 #[derive(PartialEq)]
 enum StringPiece<'a >
 {
@@ -59,42 +221,78 @@ enum StringPiece<'a >
     Char(Option<char>)
 }
 
-fn braced_parser<'a, PBL, P, PBR, O>(pbl: PBL, p: P, pbr: PBR) -> impl Parser<&'a str, Output = O>
+// RFC 8259 allows only these four between tokens
+pub(crate) fn is_json_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\n' || c == '\r'
+}
+
+// `Strict` follows RFC 8259 exactly. `Lenient` is how this parser used to work:
+// any Unicode whitespace between tokens and raw control characters other than
+// '\n', '\r' and '\t' inside strings
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub(crate) enum Grammar {
+    Strict,
+    Lenient
+}
+
+impl Grammar {
+    fn is_space(self, c: char) -> bool {
+        match self {
+            Grammar::Strict => is_json_whitespace(c),
+            Grammar::Lenient => c.is_whitespace()
+        }
+    }
+
+    pub(crate) fn is_plain_string_char(self, c: char) -> bool {
+        match self {
+            Grammar::Strict => c != '\\' && c != '"' && c >= '\u{20}',
+            Grammar::Lenient => c != '\\' && c != '"' && c != '\n' && c != '\r' && c != '\t'
+        }
+    }
+}
+
+pub(crate) fn json_space<'a>(grammar: Grammar) -> impl Parser<&'a str, Output = ()> {
+    skip_many(satisfy(move |c: char| grammar.is_space(c)))
+}
+
+fn braced_parser<'a, PBL, P, PBR, O>(grammar: Grammar, pbl: PBL, p: P, pbr: PBR) -> impl Parser<&'a str, Output = O>
     where
         PBL: Parser<&'a str>,
         PBR: Parser<&'a str>,
         P: Parser<&'a str, Output = O>
 {
     between(
-        c_compre![c; c <- pbl, __ <- skip_many(space())],
-        c_compre![c; __ <- skip_many(space()), c <- pbr],
+        c_compre![c; c <- pbl, __ <- json_space(grammar)],
+        c_compre![c; __ <- json_space(grammar), c <- pbr],
         p
     )
 }
 
-fn string_part<'a>() -> impl Parser<&'a str, Output = Vec<StringPiece<'a >>> {
-    many(
-        choice(
-            (
-                attempt(take_while1(|c: char| c != '\\' && c != '"' && c != '\n' && c != '\r' && c != '\t')
-                    .map(|chars: &str| StringPiece::Ref(chars))),
-                attempt(string("\\\"").map(|_|StringPiece::Ref("\""))),
-                attempt(string("\\\\").map(|_|StringPiece::Ref("\\"))),
-                attempt(string("\\n").map(|_|StringPiece::Ref("\n"))),
-                attempt(string("\\t").map(|_|StringPiece::Ref("\t"))),
-                attempt(string("\\/").map(|_|StringPiece::Ref("/"))),
-                attempt(string("\\r").map(|_|StringPiece::Ref("\r"))),
-                attempt(string("\\f").map(|_|StringPiece::Ref("\u{000c}"))),
-                attempt(string("\\b").map(|_|StringPiece::Ref("\u{0008}"))),
-                attempt(unicode_char().map(|s|StringPiece::Char(s))),
-            )
+fn string_piece<'a>(grammar: Grammar) -> impl Parser<&'a str, Output = StringPiece<'a >> {
+    choice(
+        (
+            attempt(take_while1(move |c: char| grammar.is_plain_string_char(c))
+                .map(|chars: &str| StringPiece::Ref(chars))),
+            attempt(string("\\\"").map(|_|StringPiece::Ref("\""))),
+            attempt(string("\\\\").map(|_|StringPiece::Ref("\\"))),
+            attempt(string("\\n").map(|_|StringPiece::Ref("\n"))),
+            attempt(string("\\t").map(|_|StringPiece::Ref("\t"))),
+            attempt(string("\\/").map(|_|StringPiece::Ref("/"))),
+            attempt(string("\\r").map(|_|StringPiece::Ref("\r"))),
+            attempt(string("\\f").map(|_|StringPiece::Ref("\u{000c}"))),
+            attempt(string("\\b").map(|_|StringPiece::Ref("\u{0008}"))),
+            attempt(unicode_char().map(|s|StringPiece::Char(s))),
         )
     )
 }
 
-fn string_parser_inner<'a>() -> impl Parser<&'a str, Output = SmolStr> {
+fn string_part<'a>(grammar: Grammar) -> impl Parser<&'a str, Output = Vec<StringPiece<'a >>> {
+    many(string_piece(grammar))
+}
+
+fn string_parser_inner<'a>(grammar: Grammar) -> impl Parser<&'a str, Output = SmolStr> {
     c_hx_do! {
-        x <- between(char('"'), char('"'), string_part());
+        x <- between(char('"'), char('"'), string_part(grammar));
         {
             let cap = x.iter().fold(0, |acc, s|
                 acc +
@@ -138,19 +336,14 @@ fn string_parser_inner<'a>() -> impl Parser<&'a str, Output = SmolStr> {
     }
 }
 
-fn string_parser<'a>() -> impl Parser<&'a str, Output = Node> {
-    string_parser_inner().map(|x| Node::String(x))
+fn string_parser<'a>(grammar: Grammar) -> impl Parser<&'a str, Output = Node> {
+    string_parser_inner(grammar).map(|x| Node::String(x))
 }
 
 fn digit_sequence<'a>() -> impl Parser<&'a str, Output = &'a str> {
     take_while1(|c: char| c >= '0' && c <= '9')
 }
 
-#[inline(always)]
-fn power(lhs: f64, rhs: f64) -> f64 {
-    lhs.powf(rhs)
-}
-
 fn trailing_digit_sequence<'a>() -> impl Parser<&'a str, Output = &'a str> {
     c_hx_do! {
         __ <- char('.'),
@@ -159,87 +352,41 @@ fn trailing_digit_sequence<'a>() -> impl Parser<&'a str, Output = &'a str> {
     }
 }
 
-fn exponent_parser<'a>() -> impl Parser<&'a str, Output = f64> {
+fn exponent_parser<'a>() -> impl Parser<&'a str, Output = &'a str> {
     c_hx_do!{
         __ <- satisfy(|c: char| c == 'e' || c == 'E'),
-        sign_char <- optional(satisfy(|c: char| c == '+' || c == '-')),
+        ___ <- optional(satisfy(|c: char| c == '+' || c == '-')),
         digits <- digit_sequence();
-        {
-            let sign = match sign_char {
-                Some('-') => -1.0,
-                _ => 1.0
-            };
-            let mut acc = 0;
-            for c in digits.as_bytes() {
-                acc = acc * 10 + (c - b'0') as u64;
-            }
-            power(10.0, sign * acc as f64)
-        }
+        digits
     }
 }
 
-#[derive(PartialEq, Copy, Clone)]
-enum NumberPrefix<'a >
-{
-    LeadingZero,
-    Digits(char, &'a str)
+fn leading_zero_parser <'a>() -> impl Parser<&'a str, Output = &'a str> {
+    string("0")
 }
 
-fn leading_zero_parser <'a>() -> impl Parser<&'a str, Output = NumberPrefix<'a >> {
-    char('0').map(|_| NumberPrefix::LeadingZero)
-}
-
-fn leading_digits_parser <'a>() -> impl Parser<&'a str, Output = NumberPrefix<'a >> {
-    c_hx_do! {
-        leading_digit <- satisfy(|c: char| c >= '1' && c <= '9'),
-        digs <- optional(digit_sequence());
-        NumberPrefix::Digits(leading_digit, digs.unwrap_or(""))
-    }
+fn leading_digits_parser <'a>() -> impl Parser<&'a str, Output = &'a str> {
+    recognize((
+        satisfy(|c: char| c >= '1' && c <= '9'),
+        optional(digit_sequence())
+    ))
 }
 
-fn leading_parser <'a>() -> impl Parser<&'a str, Output = NumberPrefix<'a >> {
+fn leading_parser <'a>() -> impl Parser<&'a str, Output = &'a str> {
     choice((
         attempt(leading_digits_parser()),
         attempt(leading_zero_parser()),
     ))
 }
 
+// The grammar only picks the number out, the value is up to std, which rounds correctly
 fn number_parser<'a>() -> impl Parser<&'a str, Output = Node> {
-    c_hx_do! {
-        minus_sign <- optional(char('-')),
-        leading <- leading_parser(),
-        trail <- optional(trailing_digit_sequence()),
-        exp <- optional(exponent_parser());
-        {
-            Node::Number({
-                let mut acc = match leading {
-                    NumberPrefix::LeadingZero => 0.0,
-                    NumberPrefix::Digits(leading_digit, l_digs) => {
-                        let mut l = (leading_digit as u8 - b'0') as u64;
-                        for c in l_digs.as_bytes() {
-                            l =  l * 10 + (c  - b'0') as u64;
-                        }
-                        l as f64
-                    }
-                };
-                if let Some(t_digs) = trail {
-                    let mut divider = 1.0;
-                    for c in t_digs.as_bytes() {
-                        divider /= 10.0;
-                        acc += (c  - b'0') as f64 * divider;
-                    }
-                }
-                if let Some(exponent) = exp {
-                    acc *= exponent;
-                }
-                if let Some(_) = minus_sign {
-                    -acc
-                } else {
-                    acc
-                }
-            })
-        }
-    }
+    recognize((
+        optional(char('-')),
+        leading_parser(),
+        optional(trailing_digit_sequence()),
+        optional(exponent_parser())
+    )).map(|number: &str| Node::Number(number.parse().unwrap()))
 }
 
 fn bool_parser<'a>() -> impl Parser<&'a str, Output = Node> {
@@ -259,61 +406,59 @@ fn null_parser<'a>() -> impl Parser<&'a str, Output = Node> {
     }
 }
 
-macro_rules! ref_parser {
-    ($parser_fn:ident) => {
-        parser(|input| {
-            let _: &mut &str = input;
-            $parser_fn().parse_stream(input).into_result()
-        })
-    }
-}
+// Arrays and objects may be nested this deep, deeper input is an error instead of a stack overflow.
+// Combine parsers take a lot of stack in a debug build, 64 levels still fit into the 2 MiB of a
+// spawned thread
+pub const MAX_DEPTH: usize = 64;
 
-fn primitive_parser<'a>() -> impl Parser<&'a str, Output = Node> {
+fn primitive_parser<'a>(grammar: Grammar, depth: usize) -> impl Parser<&'a str, Output = Node> {
     let possible_parser = bool_parser()
         .or(number_parser())
-        .or(string_parser())
+        .or(string_parser(grammar))
         .or(null_parser())
-        .or(ref_parser!(array_parser))
-        .or(ref_parser!(dictionary_parser));
+        .or(nested!(depth + 1, array_parser(grammar, depth + 1)))
+        .or(nested!(depth + 1, dictionary_parser(grammar, depth + 1)));
 
     c_hx_do! {
-        __ <- skip_many(space()),
+        __ <- json_space(grammar),
         pars <- possible_parser,
-        ___ <- skip_many(space());
+        ___ <- json_space(grammar);
         pars
     }
 }
 
-fn array_parser<'a>() -> impl Parser<&'a str, Output = Node> {
+fn array_parser<'a>(grammar: Grammar, depth: usize) -> impl Parser<&'a str, Output = Node> {
     braced_parser(
+        grammar,
         char('['),
-        sep_by(primitive_parser(), char(',')),
+        sep_by(primitive_parser(grammar, depth), char(',')),
         char(']')
     ).map(|nodes: Vec<Node>|
         Node::Array(nodes)
     )
 }
 
-fn pair_parser<'a>() -> impl Parser<&'a str, Output = Option<(SmolStr, Node)>> {
+fn pair_parser<'a>(grammar: Grammar, depth: usize) -> impl Parser<&'a str, Output = Option<(SmolStr, Node)>> {
     let str_parser = c_hx_do!{
-        __ <- skip_many(space()),
-        stp <- string_parser_inner(),
-        ___ <- skip_many(space());
+        __ <- json_space(grammar),
+        stp <- string_parser_inner(grammar),
+        ___ <- json_space(grammar);
         stp
     };
 
     c_hx_do!{
         l <- str_parser,
         __ <- char(':'),
-        r <- primitive_parser();
+        r <- primitive_parser(grammar, depth);
         Some((l, r))
     }
 }
 
-fn dictionary_parser<'a>() -> impl Parser<&'a str, Output = Node> {
+fn dictionary_parser<'a>(grammar: Grammar, depth: usize) -> impl Parser<&'a str, Output = Node> {
     braced_parser(
+        grammar,
         char('{'),
-        sep_by(pair_parser(), char(',')),
+        sep_by(pair_parser(grammar, depth), char(',')),
         char('}')
     ).map(|mut pairs: Vec<Option<(SmolStr, Node)>>| {
         let mut dict = HashMap::with_capacity(pairs.len());
@@ -327,20 +472,68 @@ fn dictionary_parser<'a>() -> impl Parser<&'a str, Output = Node> {
     })
 }
 
-fn json_parser<'a>() -> impl Parser<&'a str, Output = Node> {
+// `depth` is of arrays and objects at the top, 1 for a whole document
+fn json_parser<'a>(grammar: Grammar, depth: usize) -> impl Parser<&'a str, Output = Node> {
     null_parser()
         .or(bool_parser())
         .or(number_parser())
-        .or(string_parser())
-        .or(array_parser())
-        .or(dictionary_parser())
+        .or(string_parser(grammar))
+        .or(array_parser(grammar, depth))
+        .or(dictionary_parser(grammar, depth))
 }
 
+// Follows RFC 8259: only space, tab, line feed and carriage return between tokens,
+// no raw control characters inside strings
 pub fn parse_json(content: &str) -> Result<Node, String> {
+    parse_json_with_grammar(content, Grammar::Strict)
+}
+
+// The old behavior, for inputs which relied on it: any Unicode whitespace (like U+00A0)
+// between tokens, and raw control characters except '\n', '\r' and '\t' inside strings
+pub fn parse_json_lenient(content: &str) -> Result<Node, String> {
+    parse_json_with_grammar(content, Grammar::Lenient)
+}
+
+// Limits stricter than RFC 8259, for documents which have to be read by older parsers as well
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct ParseOptions {
+    // RFC 4627 wanted an object or an array at the top level, RFC 8259 allows any value
+    pub containers_only: bool,
+    // how many arrays and objects may be nested into each other, `MAX_DEPTH` at most
+    pub max_depth: usize
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            containers_only: false,
+            max_depth: MAX_DEPTH
+        }
+    }
+}
+
+// `parse_json` with default options
+pub fn parse_json_with(content: &str, options: ParseOptions) -> Result<Node, String> {
+    let top = content.trim_start_matches(is_json_whitespace);
+    if options.containers_only && !top.starts_with('[') && !top.starts_with('{') {
+        return Err("an object or an array is expected at the top level".to_string());
+    }
+    // as if the document was nested into containers already
+    let depth = MAX_DEPTH - options.max_depth.min(MAX_DEPTH) + 1;
+    parse_json_at_depth(content, Grammar::Strict, depth)
+}
+
+fn parse_json_with_grammar(content: &str, grammar: Grammar) -> Result<Node, String> {
+    parse_json_at_depth(content, grammar, 1)
+}
+
+// A value which is nested into `depth - 1` containers, for parts of a document parsed on
+// their own. Nesting is limited by `MAX_DEPTH` as if the containers were still around
+pub(crate) fn parse_json_at_depth(content: &str, grammar: Grammar, depth: usize) -> Result<Node, String> {
     let mut parser = c_hx_do!{
-        __ <- skip_many(space()),
-        json <- json_parser(),
-        ___ <- skip_many(space()),
+        __ <- json_space(grammar),
+        json <- json_parser(grammar, depth),
+        ___ <- json_space(grammar),
         ____ <- eof();
         json
     };
@@ -0,0 +1,186 @@
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::thread;
+use crate::{Node, Grammar, parse_json, parse_json_at_depth, is_json_whitespace};
+use crate::lazy::{skip_whitespace, skim_value};
+
+// Below this many elements threads cost more than they give
+const MIN_PARALLEL_ELEMENTS: usize = 64;
+// Every worker gets a few chunks, so slow chunks don't hold everybody else
+const CHUNKS_PER_THREAD: usize = 4;
+
+fn default_threads() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+// Ranges of elements of a top level array, or None when it is something else.
+// This is only a quick scan for brackets, elements are checked when they are parsed
+fn element_ranges(content: &str) -> Option<Vec<Range<usize>>> {
+    let mut pos = skip_whitespace(content, 0);
+    if content.as_bytes().get(pos) != Some(&b'[') {
+        return None;
+    }
+    let mut ranges = Vec::new();
+    pos = skip_whitespace(content, pos + 1);
+    if content.as_bytes().get(pos) == Some(&b']') {
+        pos += 1;
+    } else {
+        loop {
+            let start = skip_whitespace(content, pos);
+            let end = skim_value(content, start).ok()?;
+            ranges.push(start..end);
+            pos = skip_whitespace(content, end);
+            match content.as_bytes().get(pos) {
+                Some(b',') => pos += 1,
+                Some(b']') => {
+                    pos += 1;
+                    break;
+                },
+                _ => return None
+            }
+        }
+    }
+    if skip_whitespace(content, pos) == content.len() {
+        Some(ranges)
+    } else {
+        None
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+// Workers are spawned by the first parallel parse and stay for the whole program, more of them
+// are added when a caller asks for more threads than there are. Jobs can't borrow from the
+// caller, so they get the input as an `Arc<str>`: a copy is cheap next to parsing it
+struct Pool {
+    // the sender and how many workers share the receiver
+    sender: Mutex<(mpsc::Sender<Job>, usize)>,
+    receiver: Arc<Mutex<mpsc::Receiver<Job>>>
+}
+
+static POOL: OnceLock<Pool> = OnceLock::new();
+
+impl Pool {
+    fn get() -> &'static Pool {
+        POOL.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel();
+            Pool { sender: Mutex::new((sender, 0)), receiver: Arc::new(Mutex::new(receiver)) }
+        })
+    }
+
+    fn run(&self, workers: usize, jobs: impl Iterator<Item = Job>) {
+        let mut sender = self.sender.lock().expect("parser pool is poisoned");
+        while sender.1 < workers {
+            let receiver = Arc::clone(&self.receiver);
+            thread::Builder::new()
+                .name("edu-json-parser".to_string())
+                .spawn(move || work(&receiver))
+                .expect("failed to spawn a parser thread");
+            sender.1 += 1;
+        }
+        for job in jobs {
+            sender.0.send(job).expect("parser threads are gone");
+        }
+    }
+}
+
+fn work(receiver: &Mutex<mpsc::Receiver<Job>>) {
+    loop {
+        let job = match receiver.lock().expect("parser pool is poisoned").recv() {
+            Ok(job) => job,
+            Err(_) => return
+        };
+        // the caller finds out about a panic by a missing result, the worker stays
+        let _ = panic::catch_unwind(AssertUnwindSafe(job));
+    }
+}
+
+// Runs `f` over every range on `threads` workers of the pool, results keep the order of ranges
+fn run_chunked<T, F>(ranges: &[Range<usize>], threads: usize, f: F) -> Vec<T>
+    where T: Send + 'static, F: Fn(Range<usize>) -> T + Send + Sync + 'static
+{
+    let chunk_len = (ranges.len() / (threads * CHUNKS_PER_THREAD)).max(1);
+    let chunks: Vec<Vec<Range<usize>>> = ranges.chunks(chunk_len).map(<[_]>::to_vec).collect();
+    let chunk_count = chunks.len();
+    let shared = Arc::new((chunks, AtomicUsize::new(0), f));
+    let (sender, receiver) = mpsc::channel();
+    let workers = threads.min(chunk_count);
+    let jobs = (0..workers).map(|_| {
+        let shared = Arc::clone(&shared);
+        let sender = sender.clone();
+        Box::new(move || {
+            let (chunks, next_chunk, f) = &*shared;
+            loop {
+                let idx = next_chunk.fetch_add(1, Ordering::Relaxed);
+                match chunks.get(idx) {
+                    None => break,
+                    Some(chunk) => {
+                        let _ = sender.send((idx, chunk.iter().cloned().map(f).collect::<Vec<T>>()));
+                    }
+                }
+            }
+        }) as Job
+    });
+    Pool::get().run(workers, jobs);
+    drop(sender);
+    let mut done: Vec<Option<Vec<T>>> = (0..chunk_count).map(|_| None).collect();
+    for _ in 0..chunk_count {
+        let (idx, results) = receiver.recv().expect("parser thread panicked");
+        done[idx] = Some(results);
+    }
+    done.into_iter().flatten().flatten().collect()
+}
+
+pub fn parse_json_parallel(content: &str) -> Result<Node, String> {
+    parse_json_parallel_with(content, default_threads())
+}
+
+// Elements of a top level array are parsed on `threads` threads. Anything which is not
+// an array, small arrays and arrays with errors go through `parse_json`, so results
+// (and error messages) are always the same as of `parse_json`
+pub fn parse_json_parallel_with(content: &str, threads: usize) -> Result<Node, String> {
+    if threads < 2 {
+        return parse_json(content);
+    }
+    let ranges = match element_ranges(content) {
+        Some(ranges) if ranges.len() >= MIN_PARALLEL_ELEMENTS => ranges,
+        _ => return parse_json(content)
+    };
+    let shared: Arc<str> = Arc::from(content);
+    // elements are one level deep into the array
+    let parsed = run_chunked(&ranges, threads, move |range| parse_json_at_depth(&shared[range], Grammar::Strict, 2));
+    let mut items = Vec::with_capacity(parsed.len());
+    for item in parsed {
+        match item {
+            Ok(node) => items.push(node),
+            Err(_) => return parse_json(content)
+        }
+    }
+    Ok(Node::Array(items))
+}
+
+pub fn parse_json_lines_parallel(content: &str) -> Vec<(Range<usize>, Result<Node, String>)> {
+    parse_json_lines_parallel_with(content, default_threads())
+}
+
+// Newline delimited JSON: every non blank line is a value of its own
+pub fn parse_json_lines_parallel_with(content: &str, threads: usize) -> Vec<(Range<usize>, Result<Node, String>)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for line in content.split('\n') {
+        let end = start + line.len();
+        if !line.trim_matches(is_json_whitespace).is_empty() {
+            ranges.push(start..end);
+        }
+        start = end + 1;
+    }
+    let parsed = if threads > 1 && ranges.len() >= MIN_PARALLEL_ELEMENTS {
+        let shared: Arc<str> = Arc::from(content);
+        run_chunked(&ranges, threads, move |range| parse_json(&shared[range]))
+    } else {
+        ranges.iter().map(|range| parse_json(&content[range.clone()])).collect()
+    };
+    ranges.into_iter().zip(parsed).collect()
+}
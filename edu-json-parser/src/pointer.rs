@@ -0,0 +1,58 @@
+use crate::errors::{ErrorCause, pointer_panic};
+
+// Splits RFC 6901 JSON Pointer like `/a/0/b~1c` into unescaped tokens
+pub(crate) fn split_pointer(pointer: &str) -> Result<Vec<String>, ErrorCause> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !pointer.starts_with('/') {
+        return Err(ErrorCause::InvalidPointer(pointer.to_string()));
+    }
+    let mut tokens = Vec::new();
+    for raw_token in pointer[1..].split('/') {
+        let mut token = String::with_capacity(raw_token.len());
+        let mut chars = raw_token.chars();
+        while let Some(c) = chars.next() {
+            if c != '~' {
+                token.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('0') => token.push('~'),
+                Some('1') => token.push('/'),
+                _ => return Err(ErrorCause::InvalidPointer(pointer.to_string()))
+            }
+        }
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+pub(crate) fn parse_index(token: &str) -> Result<usize, ErrorCause> {
+    // RFC 6901 forbids leading zeros
+    let well_formed = !token.is_empty() &&
+        token.bytes().all(|b| b.is_ascii_digit()) &&
+        (token == "0" || !token.starts_with('0'));
+    if !well_formed {
+        return Err(ErrorCause::InvalidPointer(token.to_string()));
+    }
+    token.parse().map_err(|_| ErrorCause::InvalidPointer(token.to_string()))
+}
+
+pub fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+// Tokens of a pointer for `expect_pointer`, each one with the pointer to its parent
+pub(crate) fn expect_steps(pointer: &str, root_kind: &str) -> Vec<(String, String)> {
+    let tokens = split_pointer(pointer)
+        .unwrap_or_else(|cause| pointer_panic(pointer, "", root_kind, cause));
+    let mut parent = String::new();
+    tokens.into_iter()
+        .map(|token| {
+            let step = (token, parent.clone());
+            parent = format!("{}/{}", parent, escape_pointer_token(&step.0));
+            step
+        })
+        .collect()
+}
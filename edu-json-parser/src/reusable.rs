@@ -0,0 +1,53 @@
+use std::mem;
+use crate::Node;
+use crate::interner::KeyInterner;
+use crate::sink::{NodeBuilder, NodeStacks};
+use crate::structural::{StructuralIndex, Kernel};
+use crate::two_stage::parse_indexed;
+
+// A parser to keep around when there are lots of documents to parse. Everything it needs
+// besides the result (structural index, value stacks, a buffer for escaped strings and
+// interned keys) stays with it between calls, so after the first few documents a parse
+// allocates only the nodes it returns. Results are the same as of `parse_json`
+#[derive(Default)]
+pub struct JsonParser {
+    kernel: Option<Kernel>,
+    positions: Vec<u32>,
+    stacks: NodeStacks,
+    scratch: String,
+    interner: Option<KeyInterner>
+}
+
+impl JsonParser {
+    pub fn new() -> Self {
+        JsonParser::default()
+    }
+
+    // Equal keys of all parsed documents share one allocation
+    pub fn with_interner(interner: KeyInterner) -> Self {
+        JsonParser {
+            interner: Some(interner),
+            ..JsonParser::default()
+        }
+    }
+
+    pub fn interner(&self) -> Option<&KeyInterner> {
+        self.interner.as_ref()
+    }
+
+    pub fn parse(&mut self, content: &str) -> Result<Node, String> {
+        let kernel = *self.kernel.get_or_insert_with(Kernel::detect);
+        let positions = mem::take(&mut self.positions);
+        let index = StructuralIndex::build_reusing(content.as_bytes(), kernel, positions)
+            .map_err(|e| e.to_string())?;
+        let mut builder = NodeBuilder::with_stacks(self.interner.as_mut(), mem::take(&mut self.stacks));
+        let parsed = parse_indexed(content, &index, &mut builder, &mut self.scratch);
+        let (root, stacks) = builder.into_parts();
+        self.stacks = stacks;
+        self.positions = index.into_positions();
+        match parsed {
+            Err(e) => Err(e.to_string()),
+            Ok(_) => Ok(root)
+        }
+    }
+}
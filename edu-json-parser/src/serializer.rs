@@ -0,0 +1,63 @@
+use std::fmt::Write;
+use crate::Node;
+
+pub(crate) fn write_node(out: &mut String, node: &Node) {
+    match node {
+        Node::Null => out.push_str("null"),
+        Node::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+        Node::Number(n) => write_number(out, *n),
+        Node::String(s) => write_string(out, s),
+        Node::Array(arr) => {
+            out.push('[');
+            for (i, item) in arr.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_node(out, item);
+            }
+            out.push(']');
+        },
+        Node::Object(dict) => {
+            // HashMap gives us a random order, sorting makes output reproducible
+            let mut keys: Vec<_> = dict.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_node(out, &dict[key]);
+            }
+            out.push('}');
+        }
+    }
+}
+
+pub(crate) fn write_number(out: &mut String, n: f64) {
+    if n.is_finite() {
+        write!(out, "{}", n).unwrap();
+    } else {
+        // there is no way to express NaN or infinity in JSON
+        out.push_str("null");
+    }
+}
+
+pub(crate) fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{0008}' => out.push_str("\\b"),
+            '\u{000c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c)
+        }
+    }
+    out.push('"');
+}
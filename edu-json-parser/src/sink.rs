@@ -0,0 +1,223 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use smol_str::SmolStr;
+use combine::{Parser, eof, attempt, skip_many, sep_by};
+use combine::parser::char::char;
+use combine::parser::range::take_while;
+use crate::interner::KeyInterner;
+use crate::{Node, StringPiece, Grammar, string_piece, json_space, bool_parser, number_parser, null_parser};
+
+// The same grammar as `parse_json`, but instead of building nodes it reports every
+// value to a sink. Parsers here output nothing, so no intermediate vectors are built
+pub(crate) trait Sink {
+    // only Null, Boolean and Number come here
+    fn scalar(&mut self, node: Node);
+    fn string(&mut self, s: &str);
+    fn key(&mut self, k: &str);
+    fn start_array(&mut self);
+    fn end_array(&mut self);
+    fn start_object(&mut self);
+    fn end_object(&mut self);
+}
+
+pub(crate) struct SinkState<S> {
+    pub(crate) sink: S,
+    // buffer for strings with escapes, reused for every such string
+    pub(crate) scratch: String
+}
+
+impl<S: Sink> SinkState<S> {
+    pub(crate) fn new(sink: S, scratch: String) -> Self {
+        SinkState { sink, scratch }
+    }
+
+    fn push_piece(&mut self, piece: StringPiece) {
+        match piece {
+            StringPiece::Ref(strref) => self.scratch.push_str(strref),
+            StringPiece::Char(c) => if let Some(chr) = c { self.scratch.push(chr); }
+        }
+    }
+
+    fn emit(&mut self, s: &str, is_key: bool) {
+        if is_key {
+            self.sink.key(s);
+        } else {
+            self.sink.string(s);
+        }
+    }
+
+    fn emit_scratch(&mut self, is_key: bool) {
+        let SinkState { sink, scratch } = self;
+        if is_key {
+            sink.key(scratch);
+        } else {
+            sink.string(scratch);
+        }
+        scratch.clear();
+    }
+}
+
+fn sink_string_parser<'a, S: Sink + 'a>(state: &'a RefCell<SinkState<S>>, is_key: bool) -> impl Parser<&'a str, Output = ()> + 'a {
+    let plain = c_hx_do! {
+        s <- take_while(|c: char| Grammar::Strict.is_plain_string_char(c)),
+        __ <- char('"');
+        s
+    }.map(move |s| state.borrow_mut().emit(s, is_key));
+    let escaped = c_hx_do! {
+        __ <- skip_many(string_piece(Grammar::Strict).map(move |piece| state.borrow_mut().push_piece(piece))),
+        ___ <- char('"');
+        ()
+    }.map(move |_| state.borrow_mut().emit_scratch(is_key));
+    c_hx_do! {
+        __ <- char('"'),
+        ___ <- attempt(plain).or(escaped);
+        ()
+    }
+}
+
+fn sink_value_parser<'a, S: Sink + 'a>(state: &'a RefCell<SinkState<S>>, depth: usize) -> impl Parser<&'a str, Output = ()> + 'a {
+    let possible_parser = bool_parser()
+        .or(number_parser())
+        .or(null_parser())
+        .map(move |node| state.borrow_mut().sink.scalar(node))
+        .or(sink_string_parser(state, false))
+        .or(nested!(depth + 1, sink_array_parser(state, depth + 1)))
+        .or(nested!(depth + 1, sink_object_parser(state, depth + 1)));
+
+    c_hx_do! {
+        __ <- json_space(Grammar::Strict),
+        ___ <- possible_parser,
+        ____ <- json_space(Grammar::Strict);
+        ()
+    }
+}
+
+fn sink_array_parser<'a, S: Sink + 'a>(state: &'a RefCell<SinkState<S>>, depth: usize) -> impl Parser<&'a str, Output = ()> + 'a {
+    c_hx_do! {
+        __ <- char('[').map(move |_| state.borrow_mut().sink.start_array()),
+        ___ <- json_space(Grammar::Strict),
+        ____ <- sep_by::<(), _, _, _>(sink_value_parser(state, depth), char(',')),
+        _____ <- json_space(Grammar::Strict),
+        ______ <- char(']').map(move |_| state.borrow_mut().sink.end_array());
+        ()
+    }
+}
+
+fn sink_pair_parser<'a, S: Sink + 'a>(state: &'a RefCell<SinkState<S>>, depth: usize) -> impl Parser<&'a str, Output = ()> + 'a {
+    c_hx_do! {
+        __ <- json_space(Grammar::Strict),
+        ___ <- sink_string_parser(state, true),
+        ____ <- json_space(Grammar::Strict),
+        _____ <- char(':'),
+        ______ <- sink_value_parser(state, depth);
+        ()
+    }
+}
+
+fn sink_object_parser<'a, S: Sink + 'a>(state: &'a RefCell<SinkState<S>>, depth: usize) -> impl Parser<&'a str, Output = ()> + 'a {
+    c_hx_do! {
+        __ <- char('{').map(move |_| state.borrow_mut().sink.start_object()),
+        ___ <- json_space(Grammar::Strict),
+        ____ <- sep_by::<(), _, _, _>(sink_pair_parser(state, depth), char(',')),
+        _____ <- json_space(Grammar::Strict),
+        ______ <- char('}').map(move |_| state.borrow_mut().sink.end_object());
+        ()
+    }
+}
+
+// Feeds the whole document into the sink. On error the sink may have
+// seen a part of the document, so it should be thrown away
+pub(crate) fn parse_into<S: Sink>(content: &str, state: &RefCell<SinkState<S>>) -> Result<(), String> {
+    state.borrow_mut().scratch.clear();
+    let mut parser = c_hx_do! {
+        __ <- sink_value_parser(state, 0),
+        ___ <- eof();
+        ()
+    };
+    match parser.parse(content) {
+        Err(x) => Err(x.to_string()),
+        Ok(_) => Ok(())
+    }
+}
+
+// Stacks of a node builder, which can be kept between parses
+#[derive(Default)]
+pub(crate) struct NodeStacks {
+    values: Vec<Node>,
+    keys: Vec<SmolStr>,
+    // where values (and keys) of currently open containers start
+    frames: Vec<(usize, usize)>
+}
+
+// Builds plain nodes; with an interner, equal keys share one allocation
+pub(crate) struct NodeBuilder<'i> {
+    interner: Option<&'i mut KeyInterner>,
+    stacks: NodeStacks
+}
+
+impl<'i> NodeBuilder<'i> {
+    pub(crate) fn new(interner: Option<&'i mut KeyInterner>) -> Self {
+        NodeBuilder::with_stacks(interner, NodeStacks::default())
+    }
+
+    pub(crate) fn with_stacks(interner: Option<&'i mut KeyInterner>, stacks: NodeStacks) -> Self {
+        NodeBuilder { interner, stacks }
+    }
+
+    pub(crate) fn finish(self) -> Node {
+        self.into_parts().0
+    }
+
+    // The root and emptied stacks, ready for the next parse
+    pub(crate) fn into_parts(mut self) -> (Node, NodeStacks) {
+        let root = self.stacks.values.pop().unwrap_or(Node::Null);
+        self.stacks.values.clear();
+        self.stacks.keys.clear();
+        self.stacks.frames.clear();
+        (root, self.stacks)
+    }
+}
+
+impl<'i> Sink for NodeBuilder<'i> {
+    fn scalar(&mut self, node: Node) {
+        self.stacks.values.push(node);
+    }
+
+    fn string(&mut self, s: &str) {
+        self.stacks.values.push(Node::String(SmolStr::new(s)));
+    }
+
+    fn key(&mut self, k: &str) {
+        let key = match &mut self.interner {
+            Some(interner) => interner.intern(k),
+            None => SmolStr::new(k)
+        };
+        self.stacks.keys.push(key);
+    }
+
+    fn start_array(&mut self) {
+        self.stacks.frames.push((self.stacks.values.len(), self.stacks.keys.len()));
+    }
+
+    fn end_array(&mut self) {
+        let (values_start, _) = self.stacks.frames.pop().unwrap_or_default();
+        // not `split_off`, which gives the whole stack away for a top level array before Rust 1.78
+        let items = self.stacks.values.drain(values_start..).collect();
+        self.stacks.values.push(Node::Array(items));
+    }
+
+    fn start_object(&mut self) {
+        self.stacks.frames.push((self.stacks.values.len(), self.stacks.keys.len()));
+    }
+
+    fn end_object(&mut self) {
+        let (values_start, keys_start) = self.stacks.frames.pop().unwrap_or_default();
+        let mut dict = HashMap::with_capacity(self.stacks.values.len() - values_start);
+        let keys = self.stacks.keys.drain(keys_start..);
+        let values = self.stacks.values.drain(values_start..);
+        for (k, v) in keys.zip(values) {
+            dict.insert(k, v);
+        }
+        self.stacks.values.push(Node::Object(dict));
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use crate::Node;
+use crate::cst::{CstDocument, CstValue};
+use crate::errors::ErrorCause;
+use crate::pointer::escape_pointer_token;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Position {
+    pub offset: usize,
+    // both line and column start from 1, column is counted in chars
+    pub line: usize,
+    pub column: usize
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct NodeSpans {
+    pub value: Span,
+    // only for values which are members of an object
+    pub key: Option<Span>
+}
+
+// Side table of node locations, keyed by JSON Pointer ("" is the root, "/a/0" and so on)
+#[derive(PartialEq, Clone, Debug)]
+pub struct SpanTable {
+    spans: HashMap<String, NodeSpans>
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct LocatedError {
+    pub cause: ErrorCause,
+    pub pointer: String,
+    // boxed to keep the error small
+    pub span: Option<Box<Span>>
+}
+
+impl Display for LocatedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.span {
+            Some(span) => write!(
+                f, "{} (at '{}', line {}, column {})",
+                self.cause.to_string(), self.pointer, span.start.line, span.start.column
+            ),
+            None => write!(f, "{} (at '{}')", self.cause.to_string(), self.pointer)
+        }
+    }
+}
+
+pub fn parse_json_spanned(content: &str) -> Result<(Node, SpanTable), String> {
+    let document = CstDocument::parse(content)?;
+    let mut builder = SpanBuilder {
+        lines: LineIndex::new(content),
+        spans: HashMap::new()
+    };
+    let start = document.leading.len();
+    builder.walk(&document.root, start, String::new(), None, true);
+    Ok((document.to_node(), SpanTable { spans: builder.spans }))
+}
+
+// Turns byte offsets into positions
+pub(crate) struct LineIndex<'a> {
+    content: &'a str,
+    line_starts: Vec<usize>
+}
+
+impl<'a> LineIndex<'a> {
+    pub(crate) fn new(content: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        for (idx, b) in content.bytes().enumerate() {
+            if b == b'\n' {
+                line_starts.push(idx + 1);
+            }
+        }
+        LineIndex { content, line_starts }
+    }
+
+    pub(crate) fn position(&self, offset: usize) -> Position {
+        let line_idx = match self.line_starts.binary_search(&offset) {
+            Ok(idx) => idx,
+            Err(idx) => idx - 1
+        };
+        let line_start = self.line_starts[line_idx];
+        Position {
+            offset,
+            line: line_idx + 1,
+            column: self.content[line_start..offset].chars().count() + 1
+        }
+    }
+
+    pub(crate) fn span(&self, start: usize, end: usize) -> Span {
+        Span {
+            start: self.position(start),
+            end: self.position(end)
+        }
+    }
+}
+
+struct SpanBuilder<'a> {
+    lines: LineIndex<'a>,
+    spans: HashMap<String, NodeSpans>
+}
+
+impl<'a> SpanBuilder<'a> {
+    // Returns an offset right after the value. Values shadowed by duplicate keys
+    // are walked too, because we need their length, but they are not recorded
+    fn walk(&mut self, value: &CstValue, start: usize, pointer: String, key: Option<Span>, record: bool) -> usize {
+        let end = match value {
+            CstValue::Scalar { raw, .. } => start + raw.len(),
+            CstValue::Array(arr) => {
+                let mut offset = start + 1;
+                if arr.items.is_empty() {
+                    offset += arr.inner.len();
+                }
+                for (i, item) in arr.items.iter().enumerate() {
+                    if i > 0 {
+                        offset += 1;
+                    }
+                    offset += item.before.len();
+                    let child_pointer = format!("{}/{}", pointer, i);
+                    offset = self.walk(&item.value, offset, child_pointer, None, record);
+                    offset += item.after.len();
+                }
+                offset + 1
+            },
+            CstValue::Object(obj) => {
+                let mut offset = start + 1;
+                if obj.members.is_empty() {
+                    offset += obj.inner.len();
+                }
+                // index of the last member with each key, which is the one to record
+                let last: HashMap<&str, usize> = obj.members.iter()
+                    .enumerate()
+                    .map(|(i, member)| (member.key.as_str(), i))
+                    .collect();
+                for (i, member) in obj.members.iter().enumerate() {
+                    if i > 0 {
+                        offset += 1;
+                    }
+                    offset += member.before_key.len();
+                    let key_span = self.lines.span(offset, offset + member.key_raw.len());
+                    offset += member.key_raw.len() + member.after_key.len() + 1 + member.before_value.len();
+                    let wins = last[member.key.as_str()] == i;
+                    let child_pointer = format!("{}/{}", pointer, escape_pointer_token(&member.key));
+                    offset = self.walk(&member.value, offset, child_pointer, Some(key_span), record && wins);
+                    offset += member.after_value.len();
+                }
+                offset + 1
+            }
+        };
+        if record {
+            let value = self.lines.span(start, end);
+            self.spans.insert(pointer, NodeSpans { value, key });
+        }
+        end
+    }
+}
+
+impl SpanTable {
+    pub fn get(&self, pointer: &str) -> Option<&NodeSpans> {
+        self.spans.get(pointer)
+    }
+
+    pub fn value_span(&self, pointer: &str) -> Option<Span> {
+        self.spans.get(pointer).map(|spans| spans.value)
+    }
+
+    pub fn key_span(&self, pointer: &str) -> Option<Span> {
+        self.spans.get(pointer).and_then(|spans| spans.key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    // Attaches a location to an error returned by a getter called on the node at `pointer`.
+    // When the error is about a field which exists, the field itself is pointed at
+    pub fn locate(&self, pointer: &str, cause: ErrorCause) -> LocatedError {
+        let target = match &cause {
+            ErrorCause::WrongTypeRequested(field, _, _)
+            | ErrorCause::NotAnInteger(field, _, _)
+            | ErrorCause::NumberOutOfRange(field, _, _)
+            | ErrorCause::InexactNumber(field, _, _) =>
+                format!("{}/{}", pointer, escape_pointer_token(field)),
+            _ => pointer.to_string()
+        };
+        let span = self.value_span(&target).or_else(|| self.value_span(pointer));
+        LocatedError {
+            cause,
+            pointer: target,
+            span: span.map(Box::new)
+        }
+    }
+
+    pub fn view<'a>(&'a self, node: &'a Node) -> SpannedNode<'a> {
+        SpannedNode {
+            node,
+            pointer: String::new(),
+            spans: self
+        }
+    }
+}
+
+// Node paired with its location, so getter errors could tell where they come from
+#[derive(Clone, Debug)]
+pub struct SpannedNode<'a> {
+    node: &'a Node,
+    pointer: String,
+    spans: &'a SpanTable
+}
+
+impl<'a> SpannedNode<'a> {
+    pub fn node(&self) -> &'a Node {
+        self.node
+    }
+
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.spans.value_span(&self.pointer)
+    }
+
+    pub fn key_span(&self) -> Option<Span> {
+        self.spans.key_span(&self.pointer)
+    }
+
+    fn error(&self, cause: ErrorCause) -> LocatedError {
+        self.spans.locate(&self.pointer, cause)
+    }
+
+    pub fn get(&self, key: &str) -> Result<SpannedNode<'a>, LocatedError> {
+        match self.node.get(key) {
+            Err(cause) => Err(self.error(cause)),
+            Ok(node) => Ok(SpannedNode {
+                node,
+                pointer: format!("{}/{}", self.pointer, escape_pointer_token(key)),
+                spans: self.spans
+            })
+        }
+    }
+
+    pub fn at(&self, idx: usize) -> Result<SpannedNode<'a>, LocatedError> {
+        match self.node.as_array() {
+            None => Err(self.error(ErrorCause::NodeIsNotArray)),
+            Some(arr) => match arr.get(idx) {
+                None => Err(self.error(ErrorCause::IndexOutOfBound(idx))),
+                Some(node) => Ok(SpannedNode {
+                    node,
+                    pointer: format!("{}/{}", self.pointer, idx),
+                    spans: self.spans
+                })
+            }
+        }
+    }
+
+    pub fn get_string(&self, key: &str) -> Result<&'a str, LocatedError> {
+        self.node.get_string(key).map_err(|cause| self.error(cause))
+    }
+
+    pub fn get_as_string(&self, key: &str) -> Result<String, LocatedError> {
+        self.node.get_as_string(key).map_err(|cause| self.error(cause))
+    }
+
+    pub fn get_number(&self, key: &str) -> Result<f64, LocatedError> {
+        self.node.get_number(key).map_err(|cause| self.error(cause))
+    }
+
+    pub fn get_bool(&self, key: &str) -> Result<bool, LocatedError> {
+        self.node.get_bool(key).map_err(|cause| self.error(cause))
+    }
+}
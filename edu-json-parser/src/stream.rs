@@ -0,0 +1,131 @@
+use std::ops::Range;
+use combine::Parser;
+use crate::{Node, Grammar, json_parser, is_json_whitespace};
+
+// RFC 7464 record separator
+pub const RECORD_SEPARATOR: char = '\u{1E}';
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Framing {
+    // values simply follow each other: `{"a":1}{"b":2} 3`
+    Concatenated,
+    // every value is prefixed with RS and usually followed by LF (RFC 7464)
+    RecordSeparated
+}
+
+pub struct JsonStream<'a> {
+    content: &'a str,
+    offset: usize,
+    framing: Framing,
+    finished: bool
+}
+
+// Picks RFC 7464 framing if the first meaningful character is RS, concatenated otherwise
+pub fn parse_json_stream<'a>(content: &'a str) -> JsonStream<'a> {
+    let start = skip_whitespace(content, 0);
+    let framing = if content[start..].starts_with(RECORD_SEPARATOR) {
+        Framing::RecordSeparated
+    } else {
+        Framing::Concatenated
+    };
+    JsonStream::new(content, framing)
+}
+
+fn skip_whitespace(content: &str, from: usize) -> usize {
+    match content[from..].find(|c: char| !is_json_whitespace(c)) {
+        Some(idx) => from + idx,
+        None => content.len()
+    }
+}
+
+impl<'a> JsonStream<'a> {
+    pub fn new(content: &'a str, framing: Framing) -> Self {
+        JsonStream {
+            content,
+            offset: 0,
+            framing,
+            finished: false
+        }
+    }
+
+    pub fn framing(&self) -> Framing {
+        self.framing
+    }
+
+    fn next_concatenated(&mut self) -> Option<(Range<usize>, Result<Node, String>)> {
+        let start = skip_whitespace(self.content, self.offset);
+        if start == self.content.len() {
+            self.finished = true;
+            return None;
+        }
+        match json_parser(Grammar::Strict, 1).parse(&self.content[start..]) {
+            Ok((node, rest)) => {
+                let end = self.content.len() - rest.len();
+                self.offset = end;
+                Some((start..end, Ok(node)))
+            },
+            Err(x) => {
+                // there is no way to know where the next value starts, so we stop here
+                self.finished = true;
+                Some((start..self.content.len(), Err(x.to_string())))
+            }
+        }
+    }
+
+    fn next_record(&mut self) -> Option<(Range<usize>, Result<Node, String>)> {
+        while self.offset < self.content.len() {
+            let record_start = self.offset;
+            let record_end = match self.content[record_start..].find(RECORD_SEPARATOR) {
+                Some(idx) => record_start + idx,
+                None => self.content.len()
+            };
+            self.offset = record_end + if record_end < self.content.len() {
+                RECORD_SEPARATOR.len_utf8()
+            } else {
+                0
+            };
+
+            let start = skip_whitespace(self.content, record_start);
+            if start >= record_end {
+                // multiple RS in a row are not empty values, just skip them
+                continue;
+            }
+            let record = &self.content[start..record_end];
+            return Some(match json_parser(Grammar::Strict, 1).parse(record) {
+                Ok((node, rest)) => {
+                    let end = record_end - rest.len();
+                    if !rest.trim_start_matches(is_json_whitespace).is_empty() {
+                        (start..record_end, Err("unexpected characters after value".to_string()))
+                    } else if rest.is_empty() && is_truncatable(&node) {
+                        // RFC 7464 2.4: a top level number or literal without any whitespace
+                        // after it could be cut in the middle, so we can't trust it
+                        (start..end, Err("value is possibly truncated".to_string()))
+                    } else {
+                        (start..end, Ok(node))
+                    }
+                },
+                Err(x) => (start..record_end, Err(x.to_string()))
+            });
+        }
+        self.finished = true;
+        None
+    }
+}
+
+fn is_truncatable(node: &Node) -> bool {
+    matches!(node, Node::Number(_) | Node::Boolean(_) | Node::Null)
+}
+
+impl<'a> Iterator for JsonStream<'a> {
+    type Item = (Range<usize>, Result<Node, String>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.framing {
+            Framing::Concatenated => self.next_concatenated(),
+            Framing::RecordSeparated => self.next_record()
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::str;
+use std::convert::TryFrom;
+use crate::errors::ParseError;
+
+// Stage one of the two stage parser. It goes through the input by 64 byte blocks and
+// finds every quote which is not escaped and every structural character ({}[]:,)
+// outside of strings. Everything in between is either string contents,
+// whitespace or a scalar, and stage two looks at those by itself
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Kernel {
+    Portable,
+    Sse2,
+    Avx2
+}
+
+impl Kernel {
+    // The best kernel this CPU supports
+    pub fn detect() -> Kernel {
+        if Kernel::Avx2.is_available() {
+            Kernel::Avx2
+        } else if Kernel::Sse2.is_available() {
+            Kernel::Sse2
+        } else {
+            Kernel::Portable
+        }
+    }
+
+    pub fn is_available(self) -> bool {
+        match self {
+            Kernel::Portable => true,
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Sse2 => is_x86_feature_detected!("sse2"),
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => false
+        }
+    }
+
+    fn classifier(self) -> fn(&[u8; 64]) -> Masks {
+        match self {
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Sse2 if self.is_available() => x86::classify_sse2,
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Avx2 if self.is_available() => x86::classify_avx2,
+            _ => classify_portable
+        }
+    }
+}
+
+// One bit per byte of a block
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+struct Masks {
+    quote: u64,
+    backslash: u64,
+    structural: u64,
+    non_ascii: u64
+}
+
+fn classify_portable(block: &[u8; 64]) -> Masks {
+    let mut masks = Masks::default();
+    for (i, &b) in block.iter().enumerate() {
+        let bit = 1 << i;
+        match b {
+            b'"' => masks.quote |= bit,
+            b'\\' => masks.backslash |= bit,
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => masks.structural |= bit,
+            _ if b >= 0x80 => masks.non_ascii |= bit,
+            _ => {}
+        }
+    }
+    masks
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+    use super::Masks;
+
+    pub(super) fn classify_sse2(block: &[u8; 64]) -> Masks {
+        // the kernel is only handed out when the feature is detected
+        unsafe { sse2(block) }
+    }
+
+    pub(super) fn classify_avx2(block: &[u8; 64]) -> Masks {
+        unsafe { avx2(block) }
+    }
+
+    #[target_feature(enable = "sse2")]
+    unsafe fn sse2(block: &[u8; 64]) -> Masks {
+        let mut masks = Masks::default();
+        for lane in 0..4 {
+            let v = _mm_loadu_si128(block.as_ptr().add(lane * 16) as *const __m128i);
+            let eq = |c: u8| _mm_cmpeq_epi8(v, _mm_set1_epi8(c as i8));
+            let structural = _mm_or_si128(
+                _mm_or_si128(_mm_or_si128(eq(b'{'), eq(b'}')), _mm_or_si128(eq(b'['), eq(b']'))),
+                _mm_or_si128(eq(b':'), eq(b','))
+            );
+            let shift = lane * 16;
+            masks.quote |= (_mm_movemask_epi8(eq(b'"')) as u16 as u64) << shift;
+            masks.backslash |= (_mm_movemask_epi8(eq(b'\\')) as u16 as u64) << shift;
+            masks.structural |= (_mm_movemask_epi8(structural) as u16 as u64) << shift;
+            masks.non_ascii |= (_mm_movemask_epi8(v) as u16 as u64) << shift;
+        }
+        masks
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn avx2(block: &[u8; 64]) -> Masks {
+        let mut masks = Masks::default();
+        for lane in 0..2 {
+            let v = _mm256_loadu_si256(block.as_ptr().add(lane * 32) as *const __m256i);
+            let eq = |c: u8| _mm256_cmpeq_epi8(v, _mm256_set1_epi8(c as i8));
+            let structural = _mm256_or_si256(
+                _mm256_or_si256(_mm256_or_si256(eq(b'{'), eq(b'}')), _mm256_or_si256(eq(b'['), eq(b']'))),
+                _mm256_or_si256(eq(b':'), eq(b','))
+            );
+            let shift = lane * 32;
+            masks.quote |= (_mm256_movemask_epi8(eq(b'"')) as u32 as u64) << shift;
+            masks.backslash |= (_mm256_movemask_epi8(eq(b'\\')) as u32 as u64) << shift;
+            masks.structural |= (_mm256_movemask_epi8(structural) as u32 as u64) << shift;
+            masks.non_ascii |= (_mm256_movemask_epi8(v) as u32 as u64) << shift;
+        }
+        masks
+    }
+}
+
+// Bits of characters escaped by a backslash. Backslashes are rare,
+// so it is fine to walk them one by one
+fn escaped(backslash: u64, carry: &mut bool) -> u64 {
+    let mut escaped = 0;
+    let mut rest = backslash;
+    if *carry {
+        escaped |= 1;
+        rest &= !1;
+    }
+    *carry = false;
+    while rest != 0 {
+        let i = rest.trailing_zeros();
+        rest &= rest - 1;
+        if i == 63 {
+            *carry = true;
+        } else {
+            escaped |= 1 << (i + 1);
+            rest &= !(1 << (i + 1));
+        }
+    }
+    escaped
+}
+
+// Bit i is set when there is an odd number of set bits at positions up to i
+fn prefix_xor(mut x: u64) -> u64 {
+    x ^= x << 1;
+    x ^= x << 2;
+    x ^= x << 4;
+    x ^= x << 8;
+    x ^= x << 16;
+    x ^= x << 32;
+    x
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct StructuralIndex {
+    positions: Vec<u32>
+}
+
+impl StructuralIndex {
+    pub fn build(input: &[u8]) -> Result<StructuralIndex, ParseError> {
+        StructuralIndex::build_with(input, Kernel::detect())
+    }
+
+    // A kernel which is not available on this CPU falls back to the portable one
+    pub fn build_with(input: &[u8], kernel: Kernel) -> Result<StructuralIndex, ParseError> {
+        StructuralIndex::build_reusing(input, kernel, Vec::with_capacity(input.len() / 8))
+    }
+
+    // The same, but positions go to a buffer left from a previous index
+    pub(crate) fn build_reusing(input: &[u8], kernel: Kernel, mut positions: Vec<u32>) -> Result<StructuralIndex, ParseError> {
+        if input.len() > u32::MAX as usize {
+            return Err(ParseError::new(0, "document is too large"));
+        }
+        positions.clear();
+        let classify = kernel.classifier();
+        let mut escape_carry = false;
+        let mut in_string = 0u64;
+        let mut first_non_ascii = None;
+        let mut padded = [b' '; 64];
+        for (n, chunk) in input.chunks(64).enumerate() {
+            let block = if chunk.len() == 64 {
+                <&[u8; 64]>::try_from(chunk).unwrap()
+            } else {
+                padded[..chunk.len()].copy_from_slice(chunk);
+                &padded
+            };
+            let masks = classify(block);
+            let offset = n * 64;
+            if masks.non_ascii != 0 && first_non_ascii.is_none() {
+                first_non_ascii = Some(offset);
+            }
+            let quotes = masks.quote & !escaped(masks.backslash, &mut escape_carry);
+            // set from an opening quote up to (but not including) a closing one
+            let string_mask = prefix_xor(quotes) ^ in_string;
+            in_string = ((string_mask as i64) >> 63) as u64;
+            let mut tokens = (masks.structural & !string_mask) | quotes;
+            while tokens != 0 {
+                positions.push((offset + tokens.trailing_zeros() as usize) as u32);
+                tokens &= tokens - 1;
+            }
+        }
+        if in_string != 0 {
+            let start = positions.last().cloned().unwrap_or_default() as usize;
+            return Err(ParseError::new(start, "unterminated string"));
+        }
+        // ASCII part is checked by the masks already, the rest goes through std
+        if let Some(start) = first_non_ascii {
+            if let Err(e) = str::from_utf8(&input[start..]) {
+                return Err(ParseError::new(start + e.valid_up_to(), "invalid UTF-8"));
+            }
+        }
+        Ok(StructuralIndex { positions })
+    }
+
+    // Byte offsets of quotes and structural characters, in order
+    pub fn positions(&self) -> &[u32] {
+        &self.positions
+    }
+
+    pub(crate) fn into_positions(self) -> Vec<u32> {
+        self.positions
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
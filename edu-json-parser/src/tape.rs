@@ -0,0 +1,436 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem;
+use crate::Node;
+use crate::errors::ErrorCause;
+use crate::errors::ErrorCause::*;
+use crate::sink::{Sink, SinkState, parse_into};
+
+// Every value is one or two 64 bit words on the tape. The highest byte of a word is a tag,
+// the rest is a payload:
+//   'n', 't', 'f'   null, true and false, no payload
+//   'd'             number, the next word holds its bits
+//   '"'             string, payload is an offset into the string buffer, the next word is its length
+//   '[' and '{'     payload is an index right after the matching ']' or '}' (low 32 bits)
+//                   and a count of elements or members (high 24 bits, saturated)
+//   ']' and '}'     payload is an index of the matching '[' or '{'
+//   '-'             a key which appears again later in the same object, laid out as '"'
+// Object members are written as a key string followed by a value. Members with a '-' key
+// stay on the tape but cursors never visit them, so only the last value of a key is seen
+
+const TAG_SHIFT: u64 = 56;
+const PAYLOAD_MASK: u64 = (1 << TAG_SHIFT) - 1;
+const INDEX_MASK: u64 = 0xFFFF_FFFF;
+const COUNT_MASK: u64 = 0xFF_FFFF;
+
+fn word(tag: u8, payload: u64) -> u64 {
+    (tag as u64) << TAG_SHIFT | payload
+}
+
+// index of a value which follows the one at `idx`
+fn skip(words: &[u64], idx: usize) -> usize {
+    match (words[idx] >> TAG_SHIFT) as u8 {
+        b'[' | b'{' => (words[idx] & INDEX_MASK) as usize,
+        b'd' | b'"' => idx + 2,
+        _ => idx + 1
+    }
+}
+
+fn key_at<'s>(words: &[u64], strings: &'s str, idx: usize) -> &'s str {
+    let start = (words[idx] & PAYLOAD_MASK) as usize;
+    &strings[start..start + words[idx + 1] as usize]
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Tape {
+    words: Vec<u64>,
+    strings: String
+}
+
+#[derive(Default)]
+struct TapeBuilder {
+    words: Vec<u64>,
+    strings: String,
+    // indices of opening words of containers not closed yet, with their counts
+    open: Vec<(usize, u64)>,
+    // keys of an object being closed
+    keys: Vec<usize>
+}
+
+impl TapeBuilder {
+    fn count_value(&mut self) {
+        if let Some((_, count)) = self.open.last_mut() {
+            *count += 1;
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        self.words.push(word(b'"', self.strings.len() as u64));
+        self.words.push(s.len() as u64);
+        self.strings.push_str(s);
+    }
+
+    fn start(&mut self, tag: u8) {
+        self.count_value();
+        self.open.push((self.words.len(), 0));
+        self.words.push(word(tag, 0));
+    }
+
+    // Retags keys of the object at `start` which appear again later, returns how many there are
+    fn shadow_duplicates(&mut self, start: usize) -> u64 {
+        let TapeBuilder { words, strings, keys, .. } = self;
+        keys.clear();
+        let mut idx = start + 1;
+        while idx < words.len() {
+            keys.push(idx);
+            idx = skip(words, idx + 2);
+        }
+        // stable, so the same keys stay in the order they were written
+        keys.sort_by(|&a, &b| key_at(words, strings, a).cmp(key_at(words, strings, b)));
+        let mut shadowed = 0;
+        for i in 1..keys.len() {
+            if key_at(words, strings, keys[i - 1]) == key_at(words, strings, keys[i]) {
+                words[keys[i - 1]] = word(b'-', words[keys[i - 1]] & PAYLOAD_MASK);
+                shadowed += 1;
+            }
+        }
+        shadowed
+    }
+
+    fn end(&mut self, tag: u8) {
+        let (start, mut count) = self.open.pop().unwrap_or_default();
+        if tag == b'}' && count > 1 {
+            count -= self.shadow_duplicates(start);
+        }
+        let after = self.words.len() as u64 + 1;
+        self.words[start] |= count.min(COUNT_MASK) << 32 | after;
+        self.words.push(word(tag, start as u64));
+    }
+}
+
+impl Sink for TapeBuilder {
+    fn scalar(&mut self, node: Node) {
+        self.count_value();
+        match node {
+            Node::Boolean(true) => self.words.push(word(b't', 0)),
+            Node::Boolean(false) => self.words.push(word(b'f', 0)),
+            Node::Number(n) => {
+                self.words.push(word(b'd', 0));
+                self.words.push(n.to_bits());
+            },
+            _ => self.words.push(word(b'n', 0))
+        }
+    }
+
+    fn string(&mut self, s: &str) {
+        self.count_value();
+        self.push_str(s);
+    }
+
+    fn key(&mut self, k: &str) {
+        self.push_str(k);
+    }
+
+    fn start_array(&mut self) {
+        self.start(b'[');
+    }
+
+    fn end_array(&mut self) {
+        self.end(b']');
+    }
+
+    fn start_object(&mut self) {
+        self.start(b'{');
+    }
+
+    fn end_object(&mut self) {
+        self.end(b'}');
+    }
+}
+
+impl Tape {
+    pub fn parse(content: &str) -> Result<Tape, String> {
+        // a tape never has more than two words per input byte, so its indices fit into 32 bits
+        if content.len() > (INDEX_MASK / 2) as usize {
+            return Err("document is too large".to_string());
+        }
+        let state = RefCell::new(SinkState::new(TapeBuilder::default(), String::new()));
+        parse_into(content, &state)?;
+        let builder = state.into_inner().sink;
+        Ok(Tape {
+            words: builder.words,
+            strings: builder.strings
+        })
+    }
+
+    pub fn root(&self) -> TapeCursor<'_> {
+        TapeCursor { tape: self, idx: 0 }
+    }
+
+    pub fn to_node(&self) -> Node {
+        self.root().to_node()
+    }
+
+    // The raw tape, for those who want to scan it by themselves
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Tape>() +
+            self.words.capacity() * mem::size_of::<u64>() +
+            self.strings.capacity()
+    }
+
+    fn tag(&self, idx: usize) -> u8 {
+        (self.words[idx] >> TAG_SHIFT) as u8
+    }
+
+    fn payload(&self, idx: usize) -> u64 {
+        self.words[idx] & PAYLOAD_MASK
+    }
+
+    fn skip(&self, idx: usize) -> usize {
+        skip(&self.words, idx)
+    }
+
+    fn str_at(&self, idx: usize) -> &str {
+        key_at(&self.words, &self.strings, idx)
+    }
+}
+
+// A position on the tape, offers the same getters as `Node`
+#[derive(Copy, Clone, Debug)]
+pub struct TapeCursor<'t> {
+    tape: &'t Tape,
+    idx: usize
+}
+
+// Equal when `to_node()` would give equal nodes, members of objects are looked up by key
+// as they may be in any order
+impl<'t> PartialEq for TapeCursor<'t> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.tag(), other.tag()) {
+            (b'"', b'"') => self.as_string() == other.as_string(),
+            (b'd', b'd') => self.as_number() == other.as_number(),
+            (b'[', b'[') => self.len() == other.len() &&
+                self.elements().zip(other.elements()).all(|(a, b)| a == b),
+            (b'{', b'{') => self.len() == other.len() &&
+                self.entries().all(|(key, value)| other.get(key) == Ok(value)),
+            (tag, other_tag) => tag == other_tag
+        }
+    }
+}
+
+impl<'t> TapeCursor<'t> {
+    fn tag(&self) -> u8 {
+        self.tape.tag(self.idx)
+    }
+
+    // Index of this value on the tape
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.tag() == b'n'
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.tag() {
+            b't' => Some(true),
+            b'f' => Some(false),
+            _ => None
+        }
+    }
+
+    pub fn is_bool(&self) -> bool {
+        self.as_bool().is_some()
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self.tag() {
+            b'd' => Some(f64::from_bits(self.tape.words[self.idx + 1])),
+            _ => None
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.as_number().is_some()
+    }
+
+    pub fn as_string(&self) -> Option<&'t str> {
+        match self.tag() {
+            b'"' => Some(self.tape.str_at(self.idx)),
+            _ => None
+        }
+    }
+
+    pub fn is_string(&self) -> bool {
+        self.as_string().is_some()
+    }
+
+    pub fn to_string(&self) -> Option<String> {
+        match self.tag() {
+            b'n' => Some("null".to_string()),
+            b't' => Some("true".to_string()),
+            b'f' => Some("false".to_string()),
+            b'd' => self.as_number().map(|n| n.to_string()),
+            _ => None
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.tag() == b'['
+    }
+
+    pub fn is_object(&self) -> bool {
+        self.tag() == b'{'
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self.tag() {
+            b'n' => "null",
+            b't' | b'f' => "bool",
+            b'd' => "number",
+            b'"' => "string",
+            b'[' => "array",
+            _ => "object"
+        }
+    }
+
+    pub fn try_len(&self) -> Option<usize> {
+        if !self.is_array() && !self.is_object() {
+            return None;
+        }
+        let count = self.tape.payload(self.idx) >> 32;
+        if count < COUNT_MASK {
+            Some(count as usize)
+        } else if self.is_array() {
+            Some(self.elements().count())
+        } else {
+            Some(self.entries().count())
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.try_len().expect("it appears that node is not array or dictionary so it has no len!")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.try_len() == Some(0)
+    }
+
+    // Array elements; empty for anything else
+    pub fn elements(&self) -> TapeElements<'t> {
+        let (next, end) = self.children(b'[');
+        TapeElements { tape: self.tape, next, end }
+    }
+
+    // Object members in the order they were written; empty for anything else
+    pub fn entries(&self) -> TapeEntries<'t> {
+        let (next, end) = self.children(b'{');
+        TapeEntries { tape: self.tape, next, end }
+    }
+
+    fn children(&self, tag: u8) -> (usize, usize) {
+        if self.tag() == tag {
+            (self.idx + 1, self.tape.skip(self.idx) - 1)
+        } else {
+            (0, 0)
+        }
+    }
+
+    pub fn get_element_at(&self, idx: usize) -> Result<TapeCursor<'t>, ErrorCause> {
+        if !self.is_array() {
+            return Err(NodeIsNotArray);
+        }
+        self.elements().nth(idx).ok_or(IndexOutOfBound(idx))
+    }
+
+    pub fn get(&self, key: &str) -> Result<TapeCursor<'t>, ErrorCause> {
+        if !self.is_object() {
+            return Err(NodeIsNotADictionary);
+        }
+        self.entries()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+            .ok_or_else(|| FieldNotExist(key.to_string()))
+    }
+
+    node_getters! {
+        child: TapeCursor<'t>, element: get_element_at, string: &'t str, as_string: as_string
+    }
+
+    integer_getters! {
+        as_i8, get_i8: i8;
+        as_i16, get_i16: i16;
+        as_i32, get_i32: i32;
+        as_i64, get_i64: i64;
+        as_isize, get_isize: isize;
+        as_u8, get_u8: u8;
+        as_u16, get_u16: u16;
+        as_u32, get_u32: u32;
+        as_u64, get_u64: u64;
+        as_usize, get_usize: usize;
+    }
+
+    pub fn to_node(&self) -> Node {
+        match self.tag() {
+            b'[' => Node::Array(self.elements().map(|e| e.to_node()).collect()),
+            b'{' => {
+                let mut dict = HashMap::with_capacity(self.len());
+                for (k, v) in self.entries() {
+                    dict.insert(k.into(), v.to_node());
+                }
+                Node::Object(dict)
+            },
+            b'"' => Node::String(self.tape.str_at(self.idx).into()),
+            b'd' => Node::Number(self.as_number().unwrap_or_default()),
+            b't' => Node::Boolean(true),
+            b'f' => Node::Boolean(false),
+            _ => Node::Null
+        }
+    }
+}
+
+pub struct TapeElements<'t> {
+    tape: &'t Tape,
+    next: usize,
+    end: usize
+}
+
+impl<'t> Iterator for TapeElements<'t> {
+    type Item = TapeCursor<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+        let idx = self.next;
+        self.next = self.tape.skip(idx);
+        Some(TapeCursor { tape: self.tape, idx })
+    }
+}
+
+pub struct TapeEntries<'t> {
+    tape: &'t Tape,
+    next: usize,
+    end: usize
+}
+
+impl<'t> Iterator for TapeEntries<'t> {
+    type Item = (&'t str, TapeCursor<'t>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.end {
+            let key_idx = self.next;
+            let idx = key_idx + 2;
+            self.next = self.tape.skip(idx);
+            if self.tape.tag(key_idx) == b'"' {
+                return Some((self.tape.str_at(key_idx), TapeCursor { tape: self.tape, idx }));
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use proptest::prelude::*;
+use proptest::collection::{vec, hash_map};
+use proptest::sample::{select, Index};
+use smol_str::SmolStr;
+use crate::Node;
+
+// Proptest strategies for `Node` trees and broken JSON, for tests of this crate and of crates built on it
+
+fn nasty_char() -> impl Strategy<Value = char> {
+    prop_oneof![
+        any::<char>(),
+        select(vec![
+            '"', '\\', '/', '\u{0}', '\u{8}', '\u{c}', '\n', '\r', '\t', '\u{1f}', '\u{7f}', '\u{a0}',
+            '\u{2028}', '\u{2029}', '\u{feff}', '\u{fffd}', '\u{ffff}', '\u{10000}', '\u{1f600}', '\u{10ffff}'
+        ])
+    ]
+}
+
+// Strings with escapes, control characters, and chars beyond the BMP which need a surrogate pair
+pub fn arb_string() -> impl Strategy<Value = SmolStr> {
+    vec(nasty_char(), 0..24).prop_map(|chars| SmolStr::new(chars.into_iter().collect::<String>()))
+}
+
+// Finite numbers only, JSON has no way to express NaN and infinities
+pub fn arb_number() -> impl Strategy<Value = f64> {
+    use proptest::num::f64::{POSITIVE, NEGATIVE, NORMAL, SUBNORMAL, ZERO};
+    prop_oneof![
+        POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO,
+        any::<i64>().prop_map(|n| n as f64),
+        select(vec![
+            0.0, -0.0, 1.0, -1.0, 0.1, 1e22, 1e23, 9007199254740992.0, f64::MAX, -f64::MAX,
+            f64::MIN_POSITIVE, 5e-324, -5e-324
+        ])
+    ]
+}
+
+pub fn arb_leaf() -> impl Strategy<Value = Node> {
+    prop_oneof![
+        Just(Node::Null),
+        any::<bool>().prop_map(Node::Boolean),
+        arb_number().prop_map(Node::Number),
+        arb_string().prop_map(Node::String)
+    ]
+}
+
+// A leaf wrapped into up to `max_depth` single element arrays and objects
+pub fn arb_deep_node(max_depth: usize) -> impl Strategy<Value = Node> {
+    (arb_leaf(), vec(any::<bool>(), 0..=max_depth)).prop_map(|(leaf, levels)| {
+        levels.into_iter().fold(leaf, |node, is_array| {
+            if is_array {
+                Node::Array(vec![node])
+            } else {
+                let mut object = HashMap::new();
+                object.insert(SmolStr::new(""), node);
+                Node::Object(object)
+            }
+        })
+    })
+}
+
+// Any tree: mostly wide and shallow ones, with deep ones from time to time
+pub fn arb_node() -> impl Strategy<Value = Node> {
+    let tree = arb_leaf().prop_recursive(8, 128, 8, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..8).prop_map(Node::Array),
+            hash_map(arb_string(), inner, 0..8).prop_map(Node::Object)
+        ]
+    });
+    prop_oneof![
+        4 => tree,
+        1 => arb_deep_node(64)
+    ]
+}
+
+#[derive(Clone, Debug)]
+pub enum Mutation {
+    Replace(Index, u8),
+    Insert(Index, u8),
+    Remove(Index)
+}
+
+// bytes which matter to a JSON parser are more likely than the others
+fn arb_byte() -> impl Strategy<Value = u8> {
+    prop_oneof![
+        any::<u8>(),
+        select(b"{}[]:,\"\\0123456789.eE+-tfn ".to_vec())
+    ]
+}
+
+fn arb_mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        (any::<Index>(), arb_byte()).prop_map(|(at, b)| Mutation::Replace(at, b)),
+        (any::<Index>(), arb_byte()).prop_map(|(at, b)| Mutation::Insert(at, b)),
+        any::<Index>().prop_map(Mutation::Remove)
+    ]
+}
+
+pub fn mutate(bytes: &mut Vec<u8>, mutation: &Mutation) {
+    match mutation {
+        Mutation::Replace(at, b) if !bytes.is_empty() => {
+            let at = at.index(bytes.len());
+            bytes[at] = *b;
+        },
+        Mutation::Insert(at, b) => {
+            let at = at.index(bytes.len() + 1);
+            bytes.insert(at, *b);
+        },
+        Mutation::Remove(at) if !bytes.is_empty() => {
+            bytes.remove(at.index(bytes.len()));
+        },
+        _ => {}
+    }
+}
+
+// Serialized trees with a few bytes replaced, inserted or removed. The result is mostly
+// invalid JSON and often invalid UTF-8
+pub fn arb_mutated_json() -> impl Strategy<Value = Vec<u8>> {
+    (arb_node(), vec(arb_mutation(), 1..8)).prop_map(|(node, mutations)| {
+        let mut bytes = node.to_json().into_bytes();
+        for mutation in mutations.iter() {
+            mutate(&mut bytes, mutation);
+        }
+        bytes
+    })
+}
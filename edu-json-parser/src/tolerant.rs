@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use smol_str::SmolStr;
+use crate::{Node, MAX_DEPTH, is_json_whitespace};
+use crate::span::{Span, LineIndex};
+use crate::two_stage::{parse_atom, decode_string};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Span
+}
+
+#[derive(PartialEq, Clone, Debug)]
+enum TokenKind {
+    Open(u8),
+    Close(u8),
+    Colon,
+    Comma,
+    String(String),
+    Atom,
+    End
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize
+}
+
+// A parser for editors: it never gives up. Missing values become nulls, unexpected
+// tokens are skipped and unclosed brackets are closed at the end of input
+struct Tolerant<'a> {
+    content: &'a str,
+    pos: usize,
+    peeked: Option<Token>,
+    // closing brackets of containers which are open now
+    open: Vec<u8>,
+    lines: LineIndex<'a>,
+    diagnostics: Vec<Diagnostic>
+}
+
+fn is_delimiter(c: char) -> bool {
+    matches!(c, '{' | '}' | '[' | ']' | ':' | ',' | '"') || is_json_whitespace(c)
+}
+
+impl<'a> Tolerant<'a> {
+    fn report(&mut self, message: &str, start: usize, end: usize) {
+        let span = self.lines.span(start, end);
+        self.diagnostics.push(Diagnostic { message: message.to_string(), span });
+    }
+
+    fn scan_string(&mut self, start: usize) -> Token {
+        let bytes = self.content.as_bytes();
+        let mut i = start + 1;
+        // an unterminated string ends with its line, so the rest of a document survives
+        let (raw_end, end) = loop {
+            match bytes.get(i) {
+                None => {
+                    self.report("unterminated string", start, i);
+                    break (i, i);
+                },
+                Some(b'\n') => {
+                    self.report("unterminated string", start, i);
+                    break (i, i);
+                },
+                Some(b'"') => break (i, i + 1),
+                Some(b'\\') if i + 1 < bytes.len() && bytes[i + 1] != b'\n' => i += 2,
+                _ => i += 1
+            }
+        };
+        let raw = &self.content[start + 1..raw_end];
+        let text = match decode_string(raw) {
+            Some(text) => text.into_owned(),
+            None => {
+                self.report("invalid string", start, end);
+                raw.to_string()
+            }
+        };
+        Token { kind: TokenKind::String(text), start, end }
+    }
+
+    fn scan(&mut self) -> Token {
+        let rest = &self.content[self.pos..];
+        let start = self.content.len() - rest.trim_start_matches(is_json_whitespace).len();
+        let kind = match self.content[start..].chars().next() {
+            None => TokenKind::End,
+            Some('"') => return self.scan_string(start),
+            Some(c @ '{') | Some(c @ '[') => TokenKind::Open(c as u8),
+            Some(c @ '}') | Some(c @ ']') => TokenKind::Close(c as u8),
+            Some(':') => TokenKind::Colon,
+            Some(',') => TokenKind::Comma,
+            Some(_) => {
+                let len = self.content[start..].find(is_delimiter).unwrap_or(self.content.len() - start);
+                return Token { kind: TokenKind::Atom, start, end: start + len };
+            }
+        };
+        let end = if kind == TokenKind::End { start } else { start + 1 };
+        Token { kind, start, end }
+    }
+
+    fn peek(&mut self) -> Token {
+        if self.peeked.is_none() {
+            let token = self.scan();
+            self.pos = token.end;
+            self.peeked = Some(token);
+        }
+        self.peeked.clone().unwrap()
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.peek();
+        self.peeked = None;
+        token
+    }
+
+    fn atom(&mut self, token: &Token) -> Node {
+        match parse_atom(&self.content[token.start..token.end]) {
+            Some(node) => node,
+            None => {
+                self.report("invalid value", token.start, token.end);
+                Node::Null
+            }
+        }
+    }
+
+    // Placeholder for a value which is not there; nothing is consumed
+    fn missing(&mut self, at: usize) -> Node {
+        self.report("missing value", at, at);
+        Node::Null
+    }
+
+    // Containers deeper than MAX_DEPTH are skipped as a whole, without recursion
+    fn too_deep(&mut self) -> Node {
+        let open = self.bump();
+        self.report("nesting deeper than MAX_DEPTH", open.start, open.end);
+        let mut level = 1;
+        while level > 0 {
+            match self.bump().kind {
+                TokenKind::Open(_) => level += 1,
+                TokenKind::Close(_) => level -= 1,
+                TokenKind::End => break,
+                _ => {}
+            }
+        }
+        Node::Null
+    }
+
+    fn value(&mut self) -> Node {
+        let token = self.peek();
+        match token.kind {
+            TokenKind::Open(_) if self.open.len() >= MAX_DEPTH => self.too_deep(),
+            TokenKind::Open(b'[') => self.array(),
+            TokenKind::Open(_) => self.object(),
+            TokenKind::String(s) => {
+                self.bump();
+                Node::String(s.into())
+            },
+            TokenKind::Atom => {
+                self.bump();
+                self.atom(&token)
+            },
+            _ => self.missing(token.start)
+        }
+    }
+
+    // A closing bracket which belongs to one of outer containers closes this one too
+    fn closes_outer(&self, close: u8) -> bool {
+        self.open.iter().rev().skip(1).any(|&b| b == close)
+    }
+
+    fn array(&mut self) -> Node {
+        let open = self.bump();
+        self.open.push(b']');
+        let mut items = Vec::new();
+        let mut expect_value = true;
+        let mut after_comma = false;
+        loop {
+            let token = self.peek();
+            match token.kind {
+                TokenKind::Close(b']') => {
+                    if after_comma {
+                        self.report("trailing comma", token.start, token.end);
+                    }
+                    self.bump();
+                    break;
+                },
+                TokenKind::End => {
+                    self.report("unclosed '['", open.start, open.end);
+                    break;
+                },
+                TokenKind::Close(close) if self.closes_outer(close) => {
+                    self.report("unclosed '['", open.start, open.end);
+                    break;
+                },
+                TokenKind::Close(_) | TokenKind::Colon => {
+                    self.report("unexpected token", token.start, token.end);
+                    self.bump();
+                },
+                TokenKind::Comma => {
+                    if expect_value {
+                        items.push(self.missing(token.start));
+                    }
+                    self.bump();
+                    expect_value = true;
+                    after_comma = true;
+                },
+                _ => {
+                    if !expect_value {
+                        self.report("missing ','", token.start, token.start);
+                    }
+                    items.push(self.value());
+                    expect_value = false;
+                    after_comma = false;
+                }
+            }
+        }
+        self.open.pop();
+        Node::Array(items)
+    }
+
+    fn member(&mut self, dict: &mut HashMap<SmolStr, Node>) {
+        let key_token = self.bump();
+        let key = match key_token.kind {
+            TokenKind::String(s) => s,
+            _ => {
+                self.report("keys must be strings", key_token.start, key_token.end);
+                self.content[key_token.start..key_token.end].to_string()
+            }
+        };
+        let colon = self.peek();
+        if colon.kind == TokenKind::Colon {
+            self.bump();
+        } else {
+            self.report("missing ':'", key_token.end, key_token.end);
+        }
+        let value = match self.peek() {
+            Token { kind: TokenKind::Comma, start, .. } |
+            Token { kind: TokenKind::Close(_), start, .. } |
+            Token { kind: TokenKind::End, start, .. } => self.missing(start),
+            _ => self.value()
+        };
+        dict.insert(key.into(), value);
+    }
+
+    fn object(&mut self) -> Node {
+        let open = self.bump();
+        self.open.push(b'}');
+        let mut dict = HashMap::new();
+        let mut expect_member = true;
+        let mut after_comma = false;
+        loop {
+            let token = self.peek();
+            match token.kind {
+                TokenKind::Close(b'}') => {
+                    if after_comma {
+                        self.report("trailing comma", token.start, token.end);
+                    }
+                    self.bump();
+                    break;
+                },
+                TokenKind::End => {
+                    self.report("unclosed '{'", open.start, open.end);
+                    break;
+                },
+                TokenKind::Close(close) if self.closes_outer(close) => {
+                    self.report("unclosed '{'", open.start, open.end);
+                    break;
+                },
+                TokenKind::Close(_) | TokenKind::Colon => {
+                    self.report("unexpected token", token.start, token.end);
+                    self.bump();
+                },
+                TokenKind::Comma => {
+                    if expect_member {
+                        self.report("missing member", token.start, token.start);
+                    }
+                    self.bump();
+                    expect_member = true;
+                    after_comma = true;
+                },
+                TokenKind::Open(_) => {
+                    self.report("expected a key", token.start, token.end);
+                    self.value();
+                    expect_member = false;
+                    after_comma = false;
+                },
+                _ => {
+                    if !expect_member {
+                        self.report("missing ','", token.start, token.start);
+                    }
+                    self.member(&mut dict);
+                    expect_member = false;
+                    after_comma = false;
+                }
+            }
+        }
+        self.open.pop();
+        Node::Object(dict)
+    }
+}
+
+// Best effort node and everything which is wrong with the input. When there are
+// no diagnostics, the node is the same as `parse_json` gives
+pub fn parse_json_tolerant(content: &str) -> (Node, Vec<Diagnostic>) {
+    let mut parser = Tolerant {
+        content,
+        pos: 0,
+        peeked: None,
+        open: Vec::new(),
+        lines: LineIndex::new(content),
+        diagnostics: Vec::new()
+    };
+    let node = parser.value();
+    let rest = parser.peek();
+    if rest.kind != TokenKind::End {
+        parser.report("unexpected characters after the value", rest.start, content.len());
+        while parser.bump().kind != TokenKind::End {}
+    }
+    (node, parser.diagnostics)
+}
@@ -1,42 +1,125 @@
-use crate::{Node, parse_json};
+use std::convert::TryInto;
+use smol_str::SmolStr;
+use crate::{Node, parse_json, parse_json_spanned};
+use crate::decode::DecodeError;
+use crate::details::integer;
+use crate::errors::ErrorCause;
 
 pub trait Parsable {
-    fn parse_node(json: &Node) -> Result<Self, String>
+    fn parse_node(json: &Node) -> Result<Self, DecodeError>
         where Self: Sized;
-    fn parse_node_array(json: &Node) -> Result<Vec<Self>, String>
+    fn parse_node_array(json: &Node) -> Result<Vec<Self>, DecodeError>
         where Self: Sized
     {
-        if !json.is_array() {
-            return Err("Node is not array".to_string());
-        }
-        let length = json.len();
-        let mut result_vec = Vec::with_capacity(length);
-        for i in 0..length {
-            match Self::parse_node(&json[i]) {
-                Ok(card) => result_vec.push(card),
-                Err(error_text) => return Err(
-                    format!("Error on parsing element {}: {}", i, error_text)
-                ),
-            }
+        let items = json.as_array()
+            .ok_or_else(|| DecodeError::wrong_type("array", json.kind()))?;
+        let mut result_vec = Vec::with_capacity(items.len());
+        for (i, item) in items.iter().enumerate() {
+            result_vec.push(Self::parse_node(item).map_err(|e| e.in_element(i))?);
         }
         Ok(result_vec)
     }
-    fn parse(content: &str) -> Result<Self, String>
+    fn parse(content: &str) -> Result<Self, DecodeError>
         where Self: Sized
     {
-        let json = parse_json(content);
-        match json {
-            Ok(valid_json) => Self::parse_node(&valid_json),
-            Err(error_data) => Err(format!("Error during parsing: {}", error_data.to_string())),
-        }
+        let json = parse_json(content).map_err(|e| DecodeError::from(ErrorCause::MalformedJson(e)))?;
+        Self::parse_node(&json).map_err(|e| locate(e, content))
     }
-    fn parse_array(content: &str) -> Result<Vec<Self>, String>
+    fn parse_array(content: &str) -> Result<Vec<Self>, DecodeError>
         where Self: Sized
     {
-        let json = parse_json(content);
+        let json = parse_json(content).map_err(|e| DecodeError::from(ErrorCause::MalformedJson(e)))?;
+        Self::parse_node_array(&json).map_err(|e| locate(e, content))
+    }
+}
+
+// Spans are only worth building once something has failed
+fn locate(error: DecodeError, content: &str) -> DecodeError {
+    match parse_json_spanned(content) {
+        Ok((_, spans)) => error.with_spans(&spans),
+        Err(_) => error
+    }
+}
+
+impl Parsable for Node {
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+        Ok(json.clone())
+    }
+}
+
+impl Parsable for bool {
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+        json.as_bool().ok_or_else(|| DecodeError::wrong_type("bool", json.kind()))
+    }
+}
+
+impl Parsable for f64 {
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+        json.as_number().ok_or_else(|| DecodeError::wrong_type("number", json.kind()))
+    }
+}
+
+impl Parsable for String {
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
         match json {
-            Ok(valid_json) => Self::parse_node_array(&valid_json),
-            Err(error_data) => Err(format!("Error during parsing: {}", error_data.to_string())),
+            Node::String(s) => Ok(s.to_string()),
+            _ => Err(DecodeError::wrong_type("string", json.kind()))
         }
     }
-}
\ No newline at end of file
+}
+
+impl Parsable for SmolStr {
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+        json.as_string().ok_or_else(|| DecodeError::wrong_type("string", json.kind()))
+    }
+}
+
+// null is None, and so is a missing field when it goes through `Node::get_parsed`
+impl<T: Parsable> Parsable for Option<T> {
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+        match json {
+            Node::Null => Ok(None),
+            _ => T::parse_node(json).map(Some)
+        }
+    }
+}
+
+impl<T: Parsable> Parsable for Vec<T> {
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+        T::parse_node_array(json)
+    }
+}
+
+impl<T: Parsable, const N: usize> Parsable for [T; N] {
+    fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+        let items = T::parse_node_array(json)?;
+        let found = items.len();
+        items.try_into().map_err(|_| DecodeError::wrong_length(N, found))
+    }
+}
+
+macro_rules! parsable_integers {
+    ($($ty:ident),*) => {
+        $(
+            impl Parsable for $ty {
+                fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+                    let n = json.as_number()
+                        .ok_or_else(|| DecodeError::wrong_type(stringify!($ty), json.kind()))?;
+                    integer("", n, stringify!($ty)).map_err(|cause| {
+                        let message = match cause {
+                            ErrorCause::InexactNumber(..) => format!("{} may have been rounded, so it can't be read as `{}`", n, stringify!($ty)),
+                            _ => format!("{} can't be read as `{}`", n, stringify!($ty))
+                        };
+                        DecodeError {
+                            expected: Some(stringify!($ty)),
+                            found: Some("number"),
+                            ..DecodeError::new(message)
+                        }
+                    })
+                }
+            }
+        )*
+    }
+}
+
+parsable_integers!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
@@ -0,0 +1,292 @@
+use std::borrow::Cow;
+use std::char;
+use std::str;
+use combine::{Parser, eof};
+use crate::{Node, MAX_DEPTH, bool_parser, number_parser, null_parser, is_json_whitespace};
+use crate::sink::{Sink, NodeBuilder};
+use crate::structural::{StructuralIndex, Kernel};
+use crate::errors::ParseError;
+
+// Stage two: walks the structural index and reports values to a sink. Gaps between
+// indexed characters are checked here, so anything `parse_json` rejects is rejected too
+struct Walker<'a, 's, S> {
+    input: &'a str,
+    positions: &'a [u32],
+    next: usize,
+    // end of the last consumed token
+    cursor: usize,
+    // arrays and objects which are open now
+    depth: usize,
+    sink: &'s mut S,
+    // strings with escapes are decoded here, one at a time
+    scratch: &'s mut String
+}
+
+impl<'a, 's, S: Sink> Walker<'a, 's, S> {
+    fn error<T>(&self, what: &str) -> Result<T, ParseError> {
+        Err(ParseError::new(self.cursor, what))
+    }
+
+    fn peek(&self) -> Option<(usize, u8)> {
+        self.positions.get(self.next).map(|&p| (p as usize, self.input.as_bytes()[p as usize]))
+    }
+
+    // text between the last token and the next one (or the end)
+    fn gap(&self) -> &'a str {
+        let end = self.peek().map(|(p, _)| p).unwrap_or_else(|| self.input.len());
+        self.input[self.cursor..end].trim_matches(is_json_whitespace)
+    }
+
+    fn take(&mut self, expected: u8) -> Result<(), ParseError> {
+        if !self.gap().is_empty() {
+            return self.error("unexpected characters");
+        }
+        match self.peek() {
+            Some((p, b)) if b == expected => {
+                self.next += 1;
+                self.cursor = p + 1;
+                Ok(())
+            },
+            _ => self.error(&format!("expected '{}'", expected as char))
+        }
+    }
+
+    fn string(&mut self, is_key: bool) -> Result<(), ParseError> {
+        self.take(b'"')?;
+        let start = self.cursor;
+        let end = match self.peek() {
+            Some((end, b'"')) => end,
+            _ => return self.error("unterminated string")
+        };
+        let raw = &self.input[start..end];
+        let s = if is_plain(raw) {
+            raw
+        } else {
+            self.scratch.clear();
+            if !unescape_into(raw, self.scratch) {
+                return self.error("invalid string");
+            }
+            self.scratch.as_str()
+        };
+        if is_key {
+            self.sink.key(s);
+        } else {
+            self.sink.string(s);
+        }
+        self.next += 1;
+        self.cursor = end + 1;
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<(), ParseError> {
+        let atom = self.gap();
+        if !atom.is_empty() {
+            let node = match parse_atom(atom) {
+                Some(node) => node,
+                None => return self.error("invalid value")
+            };
+            self.sink.scalar(node);
+            self.cursor = self.peek().map(|(p, _)| p).unwrap_or_else(|| self.input.len());
+            return Ok(());
+        }
+        match self.peek() {
+            Some((_, b'"')) => self.string(false),
+            Some((_, b'[')) => self.array(),
+            Some((_, b'{')) => self.object(),
+            _ => self.error("expected a value")
+        }
+    }
+
+    fn closes(&self, close: u8) -> bool {
+        self.gap().is_empty() && matches!(self.peek(), Some((_, b)) if b == close)
+    }
+
+    fn enter(&mut self) -> Result<(), ParseError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return self.error("nesting deeper than MAX_DEPTH");
+        }
+        Ok(())
+    }
+
+    fn array(&mut self) -> Result<(), ParseError> {
+        self.take(b'[')?;
+        self.enter()?;
+        self.sink.start_array();
+        if !self.closes(b']') {
+            loop {
+                self.value()?;
+                if self.closes(b']') {
+                    break;
+                }
+                self.take(b',')?;
+            }
+        }
+        self.take(b']')?;
+        self.sink.end_array();
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn object(&mut self) -> Result<(), ParseError> {
+        self.take(b'{')?;
+        self.enter()?;
+        self.sink.start_object();
+        if !self.closes(b'}') {
+            loop {
+                self.string(true)?;
+                self.take(b':')?;
+                self.value()?;
+                if self.closes(b'}') {
+                    break;
+                }
+                self.take(b',')?;
+            }
+        }
+        self.take(b'}')?;
+        self.sink.end_object();
+        self.depth -= 1;
+        Ok(())
+    }
+}
+
+// Scalars go through the very same parsers `parse_json` uses, so numbers come out bit to bit equal
+pub(crate) fn parse_atom(atom: &str) -> Option<Node> {
+    let mut parser = bool_parser()
+        .or(number_parser())
+        .or(null_parser())
+        .skip(eof());
+    parser.parse(atom).ok().map(|(node, _)| node)
+}
+
+fn hex_digit(b: u8) -> Option<u32> {
+    (b as char).to_digit(16)
+}
+
+fn hex_code(bytes: &[u8], at: usize) -> Option<u32> {
+    bytes.get(at..at + 4)?.iter().try_fold(0, |acc, &d| hex_digit(d).map(|x| acc * 16 + x))
+}
+
+fn low_surrogate(bytes: &[u8], at: usize) -> Option<u32> {
+    if bytes.get(at..at + 2)? != b"\\u" {
+        return None;
+    }
+    hex_code(bytes, at + 2).filter(|code| (0xDC00..0xE000).contains(code))
+}
+
+fn is_plain(raw: &str) -> bool {
+    !raw.bytes().any(|b| b == b'\\' || b < 0x20)
+}
+
+// The same rules as the string grammar: no raw control characters inside, surrogate
+// pairs are joined and lone surrogates are dropped
+fn unescape_into(raw: &str, out: &mut String) -> bool {
+    let bytes = raw.as_bytes();
+    let mut plain_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            0..=0x1f => return false,
+            b'\\' => {
+                out.push_str(&raw[plain_start..i]);
+                let escape = match bytes.get(i + 1) {
+                    Some(&escape) => escape,
+                    None => return false
+                };
+                i += 2;
+                match escape {
+                    b'"' => out.push('"'),
+                    b'\\' => out.push('\\'),
+                    b'/' => out.push('/'),
+                    b'n' => out.push('\n'),
+                    b't' => out.push('\t'),
+                    b'r' => out.push('\r'),
+                    b'f' => out.push('\u{000c}'),
+                    b'b' => out.push('\u{0008}'),
+                    b'u' => {
+                        let code = match hex_code(bytes, i) {
+                            Some(code) => code,
+                            None => return false
+                        };
+                        i += 4;
+                        let code = match low_surrogate(bytes, i) {
+                            Some(low) if (0xD800..0xDC00).contains(&code) => {
+                                i += 6;
+                                0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00)
+                            },
+                            _ => code
+                        };
+                        if let Some(c) = char::from_u32(code) {
+                            out.push(c);
+                        }
+                    },
+                    _ => return false
+                }
+                plain_start = i;
+            },
+            _ => i += 1
+        }
+    }
+    out.push_str(&raw[plain_start..]);
+    true
+}
+
+pub(crate) fn decode_string(raw: &str) -> Option<Cow<'_, str>> {
+    if is_plain(raw) {
+        return Some(Cow::Borrowed(raw));
+    }
+    let mut s = String::with_capacity(raw.len());
+    if unescape_into(raw, &mut s) {
+        Some(Cow::Owned(s))
+    } else {
+        None
+    }
+}
+
+pub(crate) fn parse_indexed<S: Sink>(
+    input: &str,
+    index: &StructuralIndex,
+    sink: &mut S,
+    scratch: &mut String
+) -> Result<(), ParseError> {
+    let mut walker = Walker {
+        input,
+        positions: index.positions(),
+        next: 0,
+        cursor: 0,
+        depth: 0,
+        sink,
+        scratch
+    };
+    walker.value()?;
+    if !walker.gap().is_empty() || walker.peek().is_some() {
+        return walker.error("unexpected characters after the value");
+    }
+    Ok(())
+}
+
+// Two stage version of `parse_json`, which gives exactly the same nodes
+pub fn parse_json_simd(content: &str) -> Result<Node, String> {
+    parse_json_simd_with(content, Kernel::detect())
+}
+
+pub fn parse_json_simd_with(content: &str, kernel: Kernel) -> Result<Node, String> {
+    let index = StructuralIndex::build_with(content.as_bytes(), kernel).map_err(|e| e.to_string())?;
+    let mut builder = NodeBuilder::new(None);
+    parse_indexed(content, &index, &mut builder, &mut String::new()).map_err(|e| e.to_string())?;
+    Ok(builder.finish())
+}
+
+// The same for raw bytes, UTF-8 is validated by stage one
+pub fn parse_json_simd_bytes(content: &[u8]) -> Result<Node, String> {
+    let mut builder = NodeBuilder::new(None);
+    parse_bytes(content, &mut builder).map_err(|e| e.to_string())?;
+    Ok(builder.finish())
+}
+
+pub(crate) fn parse_bytes<S: Sink>(content: &[u8], sink: &mut S) -> Result<(), ParseError> {
+    let index = StructuralIndex::build(content)?;
+    // stage one has failed already if the content is not valid UTF-8
+    let content = unsafe { str::from_utf8_unchecked(content) };
+    parse_indexed(content, &index, sink, &mut String::new())
+}
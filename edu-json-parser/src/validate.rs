@@ -0,0 +1,23 @@
+use crate::Node;
+use crate::errors::ParseError;
+use crate::sink::Sink;
+use crate::two_stage::parse_bytes;
+
+// Throws everything away, only the grammar is checked
+struct NoSink;
+
+impl Sink for NoSink {
+    fn scalar(&mut self, _node: Node) {}
+    fn string(&mut self, _s: &str) {}
+    fn key(&mut self, _k: &str) {}
+    fn start_array(&mut self) {}
+    fn end_array(&mut self) {}
+    fn start_object(&mut self) {}
+    fn end_object(&mut self) {}
+}
+
+// Accepts exactly what `parse_json` accepts, but builds no nodes. The only allocations
+// are the structural index and a buffer for strings with escapes
+pub fn validate_json(content: &[u8]) -> Result<(), ParseError> {
+    parse_bytes(content, &mut NoSink)
+}
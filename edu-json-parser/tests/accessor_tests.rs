@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, UnwindSafe};
+    use edu_json_parser::{parse_json, parse_json_borrowed, parse_json_lazy, Document, Node, Tape};
+
+    const SOURCE: &str = r#"{"name": "Santa", "age": 99, "sizes": [120, 121, 122], "empty": {}}"#;
+
+    fn panic_message<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> String {
+        let payload = panic::catch_unwind(f).err().expect("it has to panic");
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload.downcast::<&str>().unwrap().to_string()
+        }
+    }
+
+    #[test]
+    fn index_gives_null_for_missing_values() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(Node::Number(121.0), node["sizes"][1]);
+        assert_eq!(Node::Null, node["sizes"][3]);
+        assert_eq!(Node::Null, node["nope"]["deeper"][0]);
+        assert_eq!(Node::Null, node["name"][0]);
+        assert_eq!(Node::Null, node[0]);
+
+        let borrowed = parse_json_borrowed(SOURCE).unwrap();
+        assert!(borrowed["sizes"][1].is_number());
+        assert!(borrowed["nope"]["deeper"][0].is_null());
+        assert!(borrowed["age"]["x"].is_null());
+
+        let lazy = parse_json_lazy(SOURCE).unwrap();
+        assert_eq!(Some(121.0), lazy["sizes"][1].as_number());
+        assert!(lazy["nope"]["deeper"][0].is_null());
+        assert!(lazy["sizes"][7].is_null());
+    }
+
+    #[test]
+    fn try_len_is_none_for_scalars() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(Some(4), node.try_len());
+        assert_eq!(Some(3), node["sizes"].try_len());
+        assert_eq!(Some(0), node["empty"].try_len());
+        assert_eq!(None, node["name"].try_len());
+        assert_eq!(None, node["nope"].try_len());
+
+        assert_eq!(None, parse_json_borrowed(SOURCE).unwrap()["age"].try_len());
+        assert_eq!(None, parse_json_lazy(SOURCE).unwrap()["age"].try_len());
+        assert_eq!(None, parse_json_lazy("[1 2]").unwrap().try_len());
+
+        let doc = Document::parse(SOURCE).unwrap();
+        assert_eq!(Some(3), doc.root().get("sizes").unwrap().try_len());
+        assert_eq!(None, doc.root().get("age").unwrap().try_len());
+
+        let tape = Tape::parse(SOURCE).unwrap();
+        assert_eq!(Some(4), tape.root().try_len());
+        assert_eq!(None, tape.root().get("name").unwrap().try_len());
+
+        // a scalar is not empty, though it has no length
+        assert!(!parse_json_borrowed(SOURCE).unwrap()["age"].is_empty());
+        assert!(!parse_json_lazy(SOURCE).unwrap()["age"].is_empty());
+        assert!(!doc.root().get("age").unwrap().is_empty());
+        assert!(!tape.root().get("name").unwrap().is_empty());
+        assert!(parse_json_borrowed(SOURCE).unwrap()["empty"].is_empty());
+        assert!(tape.root().get("empty").unwrap().is_empty());
+    }
+
+    #[test]
+    fn expect_accessors_give_the_value() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(3, node.expect_key("sizes").expect_len());
+        assert_eq!(&Node::Number(122.0), node.expect_key("sizes").expect_at(2));
+
+        let doc = Document::parse(SOURCE).unwrap();
+        assert_eq!(Some(120.0), doc.root().expect_key("sizes").expect_at(0).as_number());
+        let tape = Tape::parse(SOURCE).unwrap();
+        assert_eq!(Some("Santa"), tape.root().expect_key("name").as_string());
+        let lazy = parse_json_lazy(SOURCE).unwrap();
+        assert_eq!(0, lazy.expect_key("empty").expect_len());
+
+        assert_eq!(&Node::Number(122.0), node.expect_pointer("/sizes/2"));
+        assert_eq!(&node, node.expect_pointer(""));
+        assert_eq!(Some(121.0), doc.root().expect_pointer("/sizes/1").as_number());
+        assert_eq!(Some(121.0), tape.root().expect_pointer("/sizes/1").as_number());
+        assert_eq!(Some(120.0), lazy.expect_pointer("/sizes/0").as_number());
+        assert!(parse_json_borrowed(SOURCE).unwrap().expect_pointer("/empty").is_object());
+    }
+
+    #[test]
+    fn expect_accessors_tell_where_and_what() {
+        let node = parse_json(SOURCE).unwrap();
+        let message = panic_message(|| node.expect_key("name").expect_len());
+        assert!(message.starts_with("expect_len() on a node of type string"), "{}", message);
+        assert!(message.ends_with("neither an array nor a dictionary"), "{}", message);
+        let message = panic_message(|| node.expect_key("sizes").expect_at(3).clone());
+        assert!(message.contains("expect_at(3) on a node of type array"), "{}", message);
+        assert!(message.contains("Index 3 is out of bounds"), "{}", message);
+        let message = panic_message(|| node.expect_key("weight").clone());
+        assert!(message.contains("expect_key(\"weight\") on a node of type object"), "{}", message);
+
+        let tape = Tape::parse(SOURCE).unwrap();
+        let message = panic_message(|| tape.root().expect_at(0));
+        assert!(message.starts_with("expect_at(0) on a node of type object"), "{}", message);
+        let borrowed = parse_json_borrowed(SOURCE).unwrap();
+        let message = panic_message(|| borrowed.expect_key("age").expect_key("years").clone());
+        assert!(message.starts_with("expect_key(\"years\") on a node of type number"), "{}", message);
+    }
+
+    #[test]
+    fn expect_pointer_tells_the_path() {
+        let node = parse_json(SOURCE).unwrap();
+        let message = panic_message(|| node.expect_pointer("/age/years").clone());
+        assert!(message.starts_with("expect_pointer(\"/age/years\") stopped at '/age' on a node of type number"), "{}", message);
+        let message = panic_message(|| node.expect_pointer("/sizes/3").clone());
+        assert!(message.contains("stopped at '/sizes' on a node of type array: Index 3 is out of bounds"), "{}", message);
+        let message = panic_message(|| node.expect_pointer("/sizes/01").clone());
+        assert!(message.contains("'01' is not a valid JSON pointer"), "{}", message);
+        let message = panic_message(|| node.expect_pointer("sizes").clone());
+        assert!(message.contains("stopped at '' on a node of type object"), "{}", message);
+
+        let doc = Document::parse(SOURCE).unwrap();
+        let message = panic_message(|| doc.root().expect_pointer("/age/years"));
+        assert!(message.contains("stopped at '/age' on a node of type number"), "{}", message);
+        let tape = Tape::parse(SOURCE).unwrap();
+        let message = panic_message(|| tape.root().expect_pointer("/empty/x"));
+        assert!(message.contains("stopped at '/empty' on a node of type object: Field with a name 'x'"), "{}", message);
+        let message = panic_message(|| parse_json_lazy(SOURCE).unwrap().expect_pointer("/name/0").is_null());
+        assert!(message.contains("stopped at '/name' on a node of type string"), "{}", message);
+    }
+}
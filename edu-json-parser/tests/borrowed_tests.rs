@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use edu_json_parser::{parse_json, parse_json_borrowed, BorrowedNode, ErrorCause};
+
+    #[test]
+    fn strings_without_escapes_are_borrowed() {
+        let content = r#"{"name": "Santa", "quote": "ho \"ho\" ho", "tags": ["a", "", "\u0041"]}"#;
+        let node = parse_json_borrowed(content).unwrap();
+        assert!(!node.is_fully_borrowed());
+        match node.get("name").unwrap() {
+            BorrowedNode::String(Cow::Borrowed(s)) => {
+                // points right into the input
+                let offset = s.as_ptr() as usize - content.as_ptr() as usize;
+                assert_eq!("Santa", &content[offset..offset + s.len()]);
+            },
+            other => panic!("expected borrowed string, got {:?}", other)
+        }
+        match node.get("quote").unwrap() {
+            BorrowedNode::String(Cow::Owned(s)) => assert_eq!("ho \"ho\" ho", s),
+            other => panic!("expected owned string, got {:?}", other)
+        }
+        assert_eq!(Some(""), node["tags"][1].as_string());
+        assert_eq!(Some("A"), node["tags"][2].as_string());
+
+        let plain = parse_json_borrowed(r#"[{"a": "b"}, "c", 1, true, null]"#).unwrap();
+        assert!(plain.is_fully_borrowed());
+    }
+
+    #[test]
+    fn matches_owned_parser() {
+        let sources = [
+            include!("twitter.json"),
+            include!("canada.json"),
+            include!("citm_catalog.json"),
+            include!("pass01.json"),
+            " [ 1 , {\"a\" : [ ] } ] ",
+            "\"\\/\\b\\f\\n\\r\\t\\\\\""
+        ];
+        for source in sources.iter() {
+            let borrowed = parse_json_borrowed(source).unwrap();
+            assert_eq!(parse_json(source).unwrap(), borrowed.to_owned());
+            assert_eq!(borrowed, BorrowedNode::from(parse_json(source).unwrap()));
+        }
+    }
+
+    #[test]
+    fn rejects_what_owned_parser_rejects() {
+        for source in ["", "[1,]", "{\"a\":}", "\"tab\there\"", "\"\\x\"", "{} x", "01"].iter() {
+            assert!(parse_json(source).is_err());
+            assert!(parse_json_borrowed(source).is_err());
+        }
+    }
+
+    #[test]
+    fn accessors() {
+        let node = parse_json_borrowed(r#"{"s": "str", "n": 42, "b": false, "z": null, "arr": [1, 2]}"#).unwrap();
+        assert!(node.is_object());
+        assert_eq!(5, node.len());
+        assert_eq!(Ok("str"), node.get_string("s"));
+        assert_eq!(Ok(42.0), node.get_number("n"));
+        assert_eq!(Ok(false), node.get_bool("b"));
+        assert_eq!(Ok("42".to_string()), node.get_as_string("n"));
+        assert!(node["z"].is_null());
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("s".to_string(), "number", "string")), node.get_number("s"));
+        assert_eq!(Err(ErrorCause::FieldNotExist("q".to_string())), node.get_bool("q"));
+        assert_eq!(Err(ErrorCause::NodeIsNotADictionary), node["arr"].get("x"));
+        assert_eq!(Ok(BorrowedNode::Number(2.0)), node["arr"].get_element_at(1));
+        assert_eq!(Err(ErrorCause::IndexOutOfBound(2)), node["arr"].get_element_at(2));
+        assert_eq!(Err(ErrorCause::NodeIsNotArray), node.get_element_at(0));
+        assert_eq!(None, node["s"].to_string());
+        assert!(node["arr"].is_array() && node["b"].is_bool() && node["n"].is_number() && node["s"].is_string());
+    }
+}
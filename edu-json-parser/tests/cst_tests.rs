@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, CstDocument, CstValue, ErrorCause, Node};
+
+    const PACKAGE: &str = r#"  {
+    "name" : "edu-json-parser",
+    "version": "0.1.0",
+    "weight":   1.50e0,
+    "keywords": [ "json",
+                  "combine" ],
+    "empty": [  ],
+    "nested": {"a": {"b": 1}}
+}
+"#;
+
+    #[test]
+    fn render_is_lossless() {
+        let doc = CstDocument::parse(PACKAGE).unwrap();
+        assert_eq!(PACKAGE, doc.render());
+        for source in [
+            "null", " 42 ", "\t\"str\"\n", "[]", "[ ]", "{}", "{ \n }",
+            "[1,2 , 3 ]", r#"{"a":1,"a":2}"#, r#"[{"x": [ {} ] }]"#,
+            include!("pass01.json"), include!("pass02.json"), include!("pass03.json")
+        ].iter() {
+            assert_eq!(*source, CstDocument::parse(source).unwrap().render());
+        }
+    }
+
+    #[test]
+    fn converts_to_node() {
+        let doc = CstDocument::parse(PACKAGE).unwrap();
+        assert_eq!(parse_json(PACKAGE).unwrap(), doc.to_node());
+        let twitter = include!("twitter.json");
+        assert_eq!(parse_json(twitter).unwrap(), CstDocument::parse(twitter).unwrap().to_node());
+        let dup = CstDocument::parse(r#"{"a":1,"a":2}"#).unwrap();
+        assert_eq!(vec!["a", "a"], dup.root().keys());
+        assert_eq!(Ok(2.0), dup.to_node().get_number("a"));
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(CstDocument::parse("[1,]").is_err());
+        assert!(CstDocument::parse("{\"a\" 1}").is_err());
+        assert!(CstDocument::parse("{} x").is_err());
+        assert!(CstDocument::parse("").is_err());
+    }
+
+    #[test]
+    fn get_by_pointer() {
+        let doc = CstDocument::parse(PACKAGE).unwrap();
+        match doc.get("/weight").unwrap() {
+            CstValue::Scalar { raw, node } => {
+                assert_eq!("1.50e0", raw);
+                assert_eq!(&Node::Number(1.5), node);
+            },
+            _ => panic!("weight must be a scalar")
+        }
+        assert_eq!("\"combine\"", doc.get("/keywords/1").unwrap().render());
+        assert_eq!("1", doc.get("/nested/a/b").unwrap().render());
+        assert_eq!(Err(ErrorCause::FieldNotExist("c".to_string())), doc.get("/nested/c"));
+        assert_eq!(Err(ErrorCause::IndexOutOfBound(2)), doc.get("/keywords/2"));
+        assert!(doc.get("nested").is_err());
+        assert!(doc.get("/keywords/01").is_err());
+    }
+
+    #[test]
+    fn set_keeps_formatting() {
+        let mut doc = CstDocument::parse(PACKAGE).unwrap();
+        doc.set("/version", &Node::String("0.2.0".into())).unwrap();
+        doc.set_raw("/nested/a/b", "2.0").unwrap();
+        assert_eq!(
+            PACKAGE
+                .replace(r#""version": "0.1.0""#, r#""version": "0.2.0""#)
+                .replace(r#"{"b": 1}"#, r#"{"b": 2.0}"#),
+            doc.render()
+        );
+        assert!(doc.set_raw("/version", "01").is_err());
+        assert!(doc.set("/missing", &Node::Null).is_err());
+
+        let mut root = CstDocument::parse(" 1 ").unwrap();
+        root.set("", &Node::Array(vec![Node::Boolean(true)])).unwrap();
+        assert_eq!(" [true] ", root.render());
+    }
+
+    #[test]
+    fn insert_key() {
+        let mut doc = CstDocument::parse(PACKAGE).unwrap();
+        doc.insert("", "license", &Node::String("MIT".into())).unwrap();
+        assert_eq!(
+            PACKAGE.replace(
+                "\"nested\": {\"a\": {\"b\": 1}}\n}",
+                "\"nested\": {\"a\": {\"b\": 1}},\n    \"license\": \"MIT\"\n}"
+            ),
+            doc.render()
+        );
+        doc.insert("/nested", "c", &Node::Null).unwrap();
+        assert_eq!("{\"a\": {\"b\": 1}, \"c\": null}", doc.get("/nested").unwrap().render());
+
+        let mut empty = CstDocument::parse("{}").unwrap();
+        empty.insert("", "a", &Node::Number(1.0)).unwrap();
+        empty.insert("", "a", &Node::Number(2.0)).unwrap();
+        assert_eq!(r#"{"a":2}"#, empty.render());
+        assert_eq!(Err(ErrorCause::NodeIsNotADictionary), empty.insert("/a", "b", &Node::Null));
+    }
+
+    #[test]
+    fn push_and_remove_elements() {
+        let mut doc = CstDocument::parse(PACKAGE).unwrap();
+        doc.push("/keywords", &Node::String("parser".into())).unwrap();
+        assert_eq!(
+            "[ \"json\",\n                  \"combine\",\n                  \"parser\" ]",
+            doc.get("/keywords").unwrap().render()
+        );
+        assert_eq!(Ok(Node::String("combine".into())), doc.remove("/keywords/1"));
+        assert_eq!(Ok(Node::String("parser".into())), doc.remove("/keywords/1"));
+        assert_eq!("[ \"json\" ]", doc.get("/keywords").unwrap().render());
+        doc.remove("/keywords/0").unwrap();
+        assert_eq!("[ ]", doc.get("/keywords").unwrap().render());
+        doc.push("/empty", &Node::Number(1.0)).unwrap();
+        assert_eq!("[1]", doc.get("/empty").unwrap().render());
+        assert_eq!(Err(ErrorCause::IndexOutOfBound(5)), doc.remove("/empty/5"));
+        assert_eq!(Err(ErrorCause::NodeIsNotArray), doc.push("/name", &Node::Null));
+    }
+
+    #[test]
+    fn remove_keys() {
+        let mut doc = CstDocument::parse(PACKAGE).unwrap();
+        doc.remove("/nested").unwrap();
+        doc.remove("/name").unwrap();
+        let expected = r#"  {
+    "version": "0.1.0",
+    "weight":   1.50e0,
+    "keywords": [ "json",
+                  "combine" ],
+    "empty": [  ]
+}
+"#;
+        assert_eq!(expected, doc.render());
+        assert!(doc.remove("").is_err());
+        assert_eq!(Err(ErrorCause::FieldNotExist("name".to_string())), doc.remove("/name").map(|_| ()));
+    }
+
+    #[test]
+    fn remove_first_keeps_spacing() {
+        let mut doc = CstDocument::parse(r#"{"a": 1, "b": 2}"#).unwrap();
+        doc.remove("/a").unwrap();
+        assert_eq!(r#"{"b": 2}"#, doc.render());
+        let mut doc = CstDocument::parse("[1, 2, 3]").unwrap();
+        doc.remove("/0").unwrap();
+        assert_eq!("[2, 3]", doc.render());
+    }
+
+    #[test]
+    fn insert_elements() {
+        let mut doc = CstDocument::parse(PACKAGE).unwrap();
+        doc.insert_at("/keywords", 1, &Node::String("parser".into())).unwrap();
+        doc.insert_at("/keywords", 0, &Node::String("edu".into())).unwrap();
+        assert_eq!(
+            "[ \"edu\",\n                  \"json\",\n                  \"parser\",\n                  \"combine\" ]",
+            doc.get("/keywords").unwrap().render()
+        );
+        doc.insert_at("/empty", 0, &Node::Number(2.0)).unwrap();
+        doc.insert_at("/empty", 0, &Node::Number(1.0)).unwrap();
+        assert_eq!("[1,2]", doc.get("/empty").unwrap().render());
+        let mut doc = CstDocument::parse("[ 1 ]").unwrap();
+        doc.insert_at("", 0, &Node::Number(0.0)).unwrap();
+        assert_eq!("[ 0, 1 ]", doc.render());
+        assert_eq!(Err(ErrorCause::IndexOutOfBound(3)), doc.insert_at("", 3, &Node::Null));
+        assert_eq!(Err(ErrorCause::NodeIsNotArray), doc.insert_at("/0", 0, &Node::Null));
+    }
+
+    #[test]
+    fn pointer_escaping() {
+        let mut doc = CstDocument::parse(r#"{"a/b": {"c~d": 1}}"#).unwrap();
+        doc.set("/a~1b/c~0d", &Node::Number(2.0)).unwrap();
+        assert_eq!(r#"{"a/b": {"c~d": 2}}"#, doc.render());
+        assert!(doc.get("/a~2b").is_err());
+    }
+
+    #[test]
+    fn node_to_json() {
+        let node = parse_json(r#"{"b": [1, 2.5, -3e-2], "a": "q\"\n\u0001", "c": null, "d": true}"#).unwrap();
+        let json = node.to_json();
+        assert_eq!(r#"{"a":"q\"\n\u0001","b":[1,2.5,-0.03],"c":null,"d":true}"#, json);
+        assert_eq!(node, parse_json(&json).unwrap());
+        assert_eq!("null", Node::Number(f64::NAN).to_json());
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, DecodeError, ErrorCause, Node, Parsable};
+
+    #[derive(PartialEq, Debug)]
+    struct Card {
+        name: String,
+        age: u32,
+        nick: Option<String>,
+        sizes: Vec<f64>
+    }
+
+    impl Parsable for Card {
+        fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+            Ok(Card {
+                name: json.get_string("name")?.to_string(),
+                age: json.get_parsed("age")?,
+                nick: json.get_parsed("nick")?,
+                sizes: json.get_parsed("sizes")?
+            })
+        }
+    }
+
+    const CARDS: &str = r#"[
+        {"name": "Santa", "age": 99, "sizes": [120, 120, 120]},
+        {"name": "Jazz", "age": 10, "nick": "J", "sizes": [90, "60", 90]}
+    ]"#;
+
+    #[test]
+    fn errors_carry_the_path() {
+        let error = Card::parse_array(CARDS).unwrap_err();
+        assert_eq!("/1/sizes/1", error.pointer);
+        assert_eq!(Some("number"), error.expected);
+        assert_eq!(Some("string"), error.found);
+        assert_eq!("expected number, found string (at '/1/sizes/1', line 3, column 64)", error.to_string());
+
+        let error = Card::parse_array(r#"[{"name": "Santa", "age": 99}]"#).unwrap_err();
+        assert_eq!("/0/sizes", error.pointer);
+        assert_eq!("Field with a name 'sizes' does not exist", error.message);
+        // a missing value has no span, so its object is pointed at
+        assert_eq!(Some((1, 2)), error.span.map(|span| (span.start.line, span.start.column)));
+    }
+
+    #[test]
+    fn nested_values_and_getter_errors() {
+        let error = Card::parse(r#"{"name": 7, "age": 1, "sizes": []}"#).unwrap_err();
+        assert_eq!("/name", error.pointer);
+        assert_eq!(Some("string"), error.expected);
+        assert_eq!(Some("number"), error.found);
+
+        let error = Card::parse(r#"{"name": "a", "age": -1, "sizes": []}"#).unwrap_err();
+        assert_eq!(("/age", Some("u32"), Some("number")), (error.pointer.as_str(), error.expected, error.found));
+        assert_eq!("-1 can't be read as `u32`", error.message);
+
+        let node = parse_json(r#"{"cards": [{"name": "a", "age": 1, "sizes": [[]]}]}"#).unwrap();
+        let error = node.get_parsed::<Vec<Card>>("cards").unwrap_err();
+        assert_eq!("/cards/0/sizes/0", error.pointer);
+        assert_eq!(None, error.span);
+    }
+
+    #[test]
+    fn decodes_everything_else() {
+        assert_eq!(
+            Ok(vec![
+                Card { name: "Santa".to_string(), age: 99, nick: None, sizes: vec![120.0, 120.0, 120.0] },
+                Card { name: "Jazz".to_string(), age: 10, nick: Some("J".to_string()), sizes: vec![90.0] }
+            ]),
+            Card::parse_array(&CARDS.replace(r#", "60", 90"#, ""))
+        );
+        let node = parse_json(r#"[1, null, true]"#).unwrap();
+        assert_eq!(Ok(Some(1u8)), node.get_parsed_at::<Option<u8>>(0));
+        assert_eq!(Ok(None), node.get_parsed_at::<Option<u8>>(1));
+        assert_eq!("/3", node.get_parsed_at::<bool>(3).unwrap_err().pointer);
+    }
+
+    #[test]
+    fn malformed_json() {
+        let error = Card::parse("{\"name\": ").unwrap_err();
+        assert_eq!("", error.pointer);
+        assert!(error.message.starts_with("Malformed JSON"), "{}", error.message);
+        assert_eq!(
+            DecodeError::from(ErrorCause::NodeIsNotArray),
+            DecodeError::new("Trying to work with a node like it was an array, but it didn't")
+        );
+        // causes of `as_*` getters have no field
+        let error = DecodeError::from(ErrorCause::NotAnInteger(String::new(), 1.5, "u8"));
+        assert_eq!(("", Some("u8")), (error.pointer.as_str(), error.expected));
+        assert_eq!("/x", DecodeError::from(ErrorCause::WrongTypeRequested("x".into(), "u8", "bool")).pointer);
+    }
+}
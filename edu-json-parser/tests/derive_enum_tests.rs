@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::Parsable;
+
+    #[derive(Parsable, PartialEq, Debug)]
+    struct Circle {
+        radius: f64
+    }
+
+    #[derive(Parsable, PartialEq, Debug)]
+    enum Shape {
+        Empty,
+        #[parsable(rename = "dot")]
+        Point,
+        Circle(Circle),
+        Rect { width: f64, height: f64 },
+        Line(f64, f64)
+    }
+
+    #[derive(Parsable, PartialEq, Debug)]
+    #[parsable(tag = "type")]
+    enum Event {
+        Ping,
+        #[parsable(rename = "join")]
+        Join { user: String, #[parsable(default)] admin: bool },
+        Draw(Circle)
+    }
+
+    #[derive(Parsable, PartialEq, Debug)]
+    #[parsable(tag = "t", content = "c")]
+    enum Message {
+        Quit,
+        Move(i32, i32),
+        Say(String)
+    }
+
+    #[derive(Parsable, PartialEq, Debug)]
+    #[parsable(untagged)]
+    enum Value {
+        Nothing,
+        Flag(bool),
+        Circle(Circle),
+        Pair(f64, f64)
+    }
+
+    #[test]
+    fn externally_tagged() {
+        assert_eq!(Ok(Shape::Empty), Shape::parse(r#""Empty""#));
+        assert_eq!(Ok(Shape::Point), Shape::parse(r#""dot""#));
+        assert_eq!(Ok(Shape::Circle(Circle { radius: 2.0 })), Shape::parse(r#"{"Circle": {"radius": 2}}"#));
+        assert_eq!(
+            Ok(vec![Shape::Rect { width: 1.0, height: 2.0 }, Shape::Line(3.0, 4.0)]),
+            Shape::parse_array(r#"[{"Rect": {"width": 1, "height": 2}}, {"Line": [3, 4]}]"#)
+        );
+        assert_eq!(
+            "unknown variant `Point`, expected one of `Empty`, `dot`",
+            Shape::parse(r#""Point""#).unwrap_err().message
+        );
+        assert_eq!(
+            "unknown variant `Square`, expected one of `Circle`, `Rect`, `Line`",
+            Shape::parse(r#"{"Square": 1}"#).unwrap_err().message
+        );
+        assert_eq!("/Circle/radius", Shape::parse(r#"{"Circle": {"radius": "2"}}"#).unwrap_err().pointer);
+        assert_eq!(Some("string or object"), Shape::parse("1").unwrap_err().expected);
+        assert!(Shape::parse(r#"{"Circle": {"radius": 2}, "Line": [3, 4]}"#).is_err());
+    }
+
+    #[test]
+    fn internally_tagged() {
+        assert_eq!(Ok(Event::Ping), Event::parse(r#"{"type": "Ping"}"#));
+        assert_eq!(
+            Ok(Event::Join { user: "santa".to_string(), admin: false }),
+            Event::parse(r#"{"type": "join", "user": "santa"}"#)
+        );
+        assert_eq!(Ok(Event::Draw(Circle { radius: 1.0 })), Event::parse(r#"{"type": "Draw", "radius": 1}"#));
+        let error = Event::parse(r#"{"type": "Leave"}"#).unwrap_err();
+        assert_eq!(
+            ("/type", "unknown variant `Leave`, expected one of `Ping`, `join`, `Draw`"),
+            (error.pointer.as_str(), error.message.as_str())
+        );
+        assert_eq!("/type", Event::parse(r#"{"user": "santa"}"#).unwrap_err().pointer);
+        assert_eq!("/user", Event::parse(r#"{"type": "join", "user": 1}"#).unwrap_err().pointer);
+        assert_eq!(Some("object"), Event::parse(r#""Ping""#).unwrap_err().expected);
+    }
+
+    #[test]
+    fn adjacently_tagged() {
+        assert_eq!(Ok(Message::Quit), Message::parse(r#"{"t": "Quit"}"#));
+        assert_eq!(Ok(Message::Move(1, -1)), Message::parse(r#"{"t": "Move", "c": [1, -1]}"#));
+        assert_eq!(Ok(Message::Say("hi".to_string())), Message::parse(r#"{"c": "hi", "t": "Say"}"#));
+        assert_eq!("/c/1", Message::parse(r#"{"t": "Move", "c": [1, 0.5]}"#).unwrap_err().pointer);
+        assert_eq!("/c", Message::parse(r#"{"t": "Say"}"#).unwrap_err().pointer);
+        assert_eq!("/t", Message::parse(r#"{"t": "Shout", "c": "hi"}"#).unwrap_err().pointer);
+    }
+
+    #[test]
+    fn untagged_lists_every_variant_tried() {
+        assert_eq!(Ok(Value::Nothing), Value::parse(r#""Nothing""#));
+        assert_eq!(Ok(Value::Flag(true)), Value::parse("true"));
+        assert_eq!(Ok(Value::Circle(Circle { radius: 3.0 })), Value::parse(r#"{"radius": 3}"#));
+        assert_eq!(Ok(Value::Pair(1.0, 2.0)), Value::parse("[1, 2]"));
+        let error = Value::parse(r#"{"radius": "3"}"#).unwrap_err();
+        assert_eq!("", error.pointer);
+        assert_eq!(
+            "no variant of `Value` matches, tried \
+            `Nothing`: expected \"Nothing\" (at ''); \
+            `Flag`: expected bool, found object (at ''); \
+            `Circle`: expected number, found string (at '/radius'); \
+            `Pair`: expected array, found object (at '')",
+            error.message
+        );
+    }
+}
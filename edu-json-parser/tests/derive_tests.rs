@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, DecodeError, Node, Parsable};
+
+    #[derive(Parsable, PartialEq, Debug)]
+    struct Card {
+        name: String,
+        #[parsable(rename = "lastName")]
+        last_name: String,
+        age: u32,
+        nick: Option<String>,
+        sizes: [f64; 3]
+    }
+
+    #[derive(Parsable, PartialEq, Debug)]
+    struct Point(f64, f64);
+
+    #[derive(Parsable, PartialEq, Debug)]
+    struct Meters(f64);
+
+    #[derive(Parsable, PartialEq, Debug)]
+    struct Id;
+
+    fn seven() -> u8 {
+        7
+    }
+
+    #[derive(Parsable, PartialEq, Debug)]
+    struct Shape {
+        r#type: String,
+        #[parsable(default)]
+        points: Vec<Point>,
+        #[parsable(default = "seven")]
+        color: u8,
+        #[parsable(skip)]
+        cache: Option<Node>,
+        #[parsable(flatten)]
+        size: Size<Meters>
+    }
+
+    #[derive(Parsable, PartialEq, Debug)]
+    struct Size<T> {
+        width: T,
+        height: T
+    }
+
+    #[test]
+    fn named_fields() {
+        let source = r#"{"name": "Santa", "lastName": "Clous", "age": 99, "sizes": [120, 121, 122]}"#;
+        assert_eq!(
+            Ok(Card {
+                name: "Santa".to_string(),
+                last_name: "Clous".to_string(),
+                age: 99,
+                nick: None,
+                sizes: [120.0, 121.0, 122.0]
+            }),
+            Card::parse(source)
+        );
+        assert_eq!("/lastName", Card::parse(r#"{"name": "Santa", "age": 99}"#).unwrap_err().pointer);
+        assert_eq!(Some("object"), Card::parse("[]").unwrap_err().expected);
+    }
+
+    #[test]
+    fn tuples_newtypes_and_units() {
+        assert_eq!(Ok(Point(1.0, 2.0)), Point::parse("[1, 2]"));
+        assert_eq!(Ok(Meters(3.5)), Meters::parse("3.5"));
+        assert_eq!(Ok(Id), Id::parse("null"));
+        assert_eq!(Ok(vec![Point(1.0, 2.0), Point(3.0, 4.0)]), Point::parse_array("[[1, 2], [3, 4]]"));
+        assert_eq!(Err(DecodeError::wrong_length(2, 3)), Point::parse_node(&parse_json("[1, 2, 3]").unwrap()));
+        assert_eq!("/1", Point::parse(r#"[1, "2"]"#).unwrap_err().pointer);
+        assert_eq!(Some("array"), Point::parse(r#"{"0": 1, "1": 2}"#).unwrap_err().expected);
+        assert_eq!(Some("null"), Id::parse("0").unwrap_err().expected);
+    }
+
+    #[test]
+    fn field_attributes() {
+        let shape = Shape::parse(r#"{"type": "box", "width": 2, "height": 3, "cache": 1}"#).unwrap();
+        assert_eq!(
+            Shape {
+                r#type: "box".to_string(),
+                points: vec![],
+                color: 7,
+                cache: None,
+                size: Size { width: Meters(2.0), height: Meters(3.0) }
+            },
+            shape
+        );
+        let shape = Shape::parse(r#"{"type": "line", "points": [[0, 0], [1, 1]], "color": null, "width": 1, "height": 0}"#);
+        assert_eq!(Ok((2, 7)), shape.map(|shape| (shape.points.len(), shape.color)));
+        let error = Shape::parse(r#"{"type": "box", "color": 300, "width": 2, "height": 3}"#).unwrap_err();
+        assert_eq!(("/color", Some("u8")), (error.pointer.as_str(), error.expected));
+    }
+
+    #[test]
+    fn nested_errors_have_the_full_path() {
+        let source = r#"{
+            "type": "line",
+            "points": [[0, 0], [1, "1"]],
+            "width": 1, "height": 0
+        }"#;
+        let error = Shape::parse(source).unwrap_err();
+        assert_eq!("/points/1/1", error.pointer);
+        assert_eq!("expected number, found string (at '/points/1/1', line 3, column 36)", error.to_string());
+        assert_eq!("/height", Shape::parse(r#"{"type": "box", "width": 1}"#).unwrap_err().pointer);
+    }
+}
@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use edu_json_parser::{parse_json, Node};
+    use json::JsonValue;
+    use serde_json::Value;
+
+    const CORPUS: [&str; 10] = [
+        include!("twitter.json"), include!("canada.json"), include!("citm_catalog.json"),
+        include!("pass01.json"), include!("pass02.json"), include!("pass03.json"),
+        include!("../benches/many.json"), include!("../benches/spoiled.json"),
+        include!("../benches/twitter.json"), include!("../benches/citm_catalog.json")
+    ];
+
+    const SUITE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/JSONTestSuite/test_parsing/");
+
+    // serde_json is built with `float_roundtrip`, so numbers have to match to the bit
+    fn same_as_serde(node: &Node, value: &Value) -> bool {
+        match (node, value) {
+            (Node::Null, Value::Null) => true,
+            (Node::Boolean(l), Value::Bool(r)) => l == r,
+            (Node::Number(l), Value::Number(r)) => Some(l.to_bits()) == r.as_f64().map(f64::to_bits),
+            (Node::String(l), Value::String(r)) => l == r,
+            (Node::Array(l), Value::Array(r)) => {
+                l.len() == r.len() && l.iter().zip(r).all(|(l, r)| same_as_serde(l, r))
+            },
+            (Node::Object(l), Value::Object(r)) => {
+                l.len() == r.len() && r.iter().all(|(k, r)| l.get(k.as_str()).is_some_and(|l| same_as_serde(l, r)))
+            },
+            _ => false
+        }
+    }
+
+    // json keeps numbers as a decimal mantissa and exponent and turns them to f64
+    // approximately, so here they only have to be close
+    fn same_as_json(node: &Node, value: &JsonValue) -> bool {
+        match (node, value) {
+            (Node::Null, JsonValue::Null) => true,
+            (Node::Boolean(l), JsonValue::Boolean(r)) => l == r,
+            (Node::Number(l), JsonValue::Number(r)) => {
+                let r = f64::from(*r);
+                l == &r || (l - r).abs() <= 1e-14 * l.abs().max(r.abs())
+            },
+            (Node::String(l), JsonValue::String(_)) | (Node::String(l), JsonValue::Short(_)) => {
+                value.as_str() == Some(l.as_str())
+            },
+            (Node::Array(l), JsonValue::Array(r)) => {
+                l.len() == r.len() && l.iter().zip(r).all(|(l, r)| same_as_json(l, r))
+            },
+            (Node::Object(l), JsonValue::Object(r)) => {
+                l.len() == r.len() && r.iter().all(|(k, r)| l.get(k).is_some_and(|l| same_as_json(l, r)))
+            },
+            _ => false
+        }
+    }
+
+    fn compare(input: &str) -> Result<(), String> {
+        let node = parse_json(input);
+        let serde = serde_json::from_str::<Value>(input);
+        let json = json::parse(input);
+        // json takes a form feed for whitespace, so only serde_json decides what is valid
+        match (&node, &serde, &json) {
+            (Ok(node), Ok(serde), Ok(json)) => {
+                if !same_as_serde(node, serde) {
+                    return Err(format!("serde_json disagrees: {:?} vs {:?}", node, serde));
+                }
+                if !same_as_json(node, json) {
+                    return Err(format!("json disagrees: {:?} vs {:?}", node, json));
+                }
+                Ok(())
+            },
+            (Err(_), Err(_), _) => Ok(()),
+            _ => Err(format!(
+                "accepted by edu_json_parser: {}, serde_json: {}, json: {}",
+                node.is_ok(), serde.is_ok(), json.is_ok()
+            ))
+        }
+    }
+
+    // Drops chunks of chars, halving them, while the input still diverges
+    fn minimize(input: &str) -> String {
+        let mut current: Vec<char> = input.chars().collect();
+        let mut chunk = current.len() / 2;
+        while chunk > 0 {
+            let mut start = 0;
+            let mut dropped = false;
+            while start < current.len() {
+                let end = (start + chunk).min(current.len());
+                let candidate: String = current[..start].iter().chain(&current[end..]).collect();
+                if compare(&candidate).is_err() {
+                    current = candidate.chars().collect();
+                    dropped = true;
+                } else {
+                    start += chunk;
+                }
+            }
+            if !dropped {
+                chunk /= 2;
+            }
+        }
+        current.into_iter().collect()
+    }
+
+    fn assert_agree(input: &str) {
+        if let Err(divergence) = compare(input) {
+            let minimized = minimize(input);
+            panic!("{}\nminimized input: {:?}\nit gives: {}", divergence, minimized, compare(&minimized).unwrap_err());
+        }
+    }
+
+    #[test]
+    fn corpus_agrees() {
+        for source in CORPUS.iter() {
+            assert_agree(source);
+        }
+    }
+
+    #[test]
+    fn json_test_suite_agrees() {
+        for entry in fs::read_dir(SUITE).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_str().unwrap().to_string();
+            // the others are about invalid UTF-8 and lone surrogates, where libraries differ
+            if name.starts_with("y_") || name.starts_with("n_") {
+                if let Ok(source) = String::from_utf8(fs::read(&path).unwrap()) {
+                    assert_agree(&source);
+                }
+            }
+        }
+    }
+
+    // xorshift, so failures are reproducible
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[self.below(items.len() as u64) as usize]
+        }
+    }
+
+    fn space(random: &mut Random, out: &mut String) {
+        for _ in 0..random.below(3) {
+            out.push_str(random.pick(&[" ", "\t", "\n", "\r\n"]));
+        }
+    }
+
+    fn digits(random: &mut Random, out: &mut String, max: u64) {
+        for _ in 0..1 + random.below(max) {
+            out.push((b'0' + random.below(10) as u8) as char);
+        }
+    }
+
+    fn number(random: &mut Random, out: &mut String) {
+        if random.below(3) == 0 {
+            out.push('-');
+        }
+        if random.below(4) == 0 {
+            out.push('0');
+        } else {
+            out.push((b'1' + random.below(9) as u8) as char);
+            digits(random, out, 22);
+        }
+        if random.below(2) == 0 {
+            out.push('.');
+            digits(random, out, 20);
+        }
+        if random.below(3) == 0 {
+            out.push_str(random.pick(&["e", "E", "e+", "e-", "E-"]));
+            out.push_str(&random.below(280).to_string());
+        }
+    }
+
+    fn string(random: &mut Random, out: &mut String) {
+        out.push('"');
+        for _ in 0..random.below(8) {
+            out.push_str(random.pick(&[
+                "a", "Z", " ", "0", "é", "€", "𝄞", "\\\"", "\\\\", "\\/", "\\b", "\\f", "\\n", "\\r", "\\t",
+                "\\u0000", "\\u001f", "\\u00e9", "\\u20AC", "\\uD834\\uDD1E", "\\ud83d\\ude00"
+            ]));
+        }
+        out.push('"');
+    }
+
+    fn document(random: &mut Random, out: &mut String, depth: u32) {
+        space(random, out);
+        let kind = if depth == 0 { random.below(4) } else { random.below(6) };
+        match kind {
+            0 => out.push_str(random.pick(&["null", "true", "false"])),
+            1 | 2 => number(random, out),
+            3 => string(random, out),
+            4 => {
+                out.push('[');
+                for i in 0..random.below(5) {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    document(random, out, depth - 1);
+                }
+                space(random, out);
+                out.push(']');
+            },
+            _ => {
+                out.push('{');
+                for i in 0..random.below(5) {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    space(random, out);
+                    string(random, out);
+                    space(random, out);
+                    out.push(':');
+                    document(random, out, depth - 1);
+                }
+                space(random, out);
+                out.push('}');
+            }
+        }
+        space(random, out);
+    }
+
+    #[test]
+    fn random_documents_agree() {
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+        for _ in 0..2000 {
+            let mut source = String::new();
+            document(&mut random, &mut source, 5);
+            assert_agree(&source);
+        }
+    }
+}
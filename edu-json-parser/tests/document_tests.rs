@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, Document, ErrorCause, Node};
+
+    const CARDS: &str = include!("../benches/many.json");
+
+    #[test]
+    fn matches_node_parser() {
+        let sources = [
+            include!("twitter.json"),
+            include!("canada.json"),
+            include!("citm_catalog.json"),
+            include!("pass01.json"),
+            CARDS,
+            "null", " 1.5 ", "\"\\u0041\\n\"", "[]", "{}", "[[], {}, [{}]]"
+        ];
+        for source in sources.iter() {
+            let doc = Document::parse(source).unwrap();
+            assert_eq!(parse_json(source).unwrap(), doc.to_node());
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for source in ["", "[1,]", "{\"a\":}", "\"\\x\"", "{} x", "[1 2]", "{\"a\" 1}"].iter() {
+            assert!(Document::parse(source).is_err());
+        }
+    }
+
+    #[test]
+    fn duplicate_keys_are_dropped_while_building() {
+        let source = r#"{"b": 1, "a": {"x": 1, "x": [2]}, "b": 3, "c": null, "b": 4}"#;
+        let doc = Document::parse(source).unwrap();
+        let root = doc.root();
+        assert_eq!(3, root.len());
+        let keys: Vec<&str> = root.entries().map(|(k, _)| k).collect();
+        assert_eq!(vec!["a", "c", "b"], keys);
+        assert_eq!(Ok(4.0), root.get_number("b"));
+        assert_eq!(Some(1), root.get("a").unwrap().try_len());
+        assert_eq!(Ok(1), root.get("a").unwrap().get("x").map(|x| x.len()));
+        assert_eq!(Err(ErrorCause::FieldNotExist("d".to_string())), root.get("d").map(|_| ()));
+        assert_eq!(parse_json(source).unwrap(), doc.to_node());
+    }
+
+    #[test]
+    fn views_are_compared_in_place() {
+        let a = Document::parse(r#"{"x": [1, "s", {"k": null}], "y": true}"#).unwrap();
+        let b = Document::parse(r#"{"y": false, "x": [1, "s", {"k": null}], "y": true}"#).unwrap();
+        let c = Document::parse(r#"{"y": true, "x": [1, "s", {"k": false}]}"#).unwrap();
+        assert!(a.root() == b.root());
+        assert!(a.root() != c.root());
+        assert!(a.root().get("x") == b.root().get("x"));
+        assert!(a.root().get("x") != a.root().get("y"));
+        let numbers = Document::parse("[1, 1.0, 2]").unwrap();
+        let numbers = numbers.root();
+        assert!(numbers.get_element_at(0) == numbers.get_element_at(1));
+        assert!(numbers.get_element_at(1) != numbers.get_element_at(2));
+    }
+
+    #[test]
+    fn reparse_keeps_buffers() {
+        let mut doc = Document::parse(CARDS).unwrap();
+        doc.reparse(CARDS).unwrap();
+        let usage = doc.memory_usage();
+        for source in ["[1, 2]", CARDS, "{\"a\": \"b\"}", "null"].iter() {
+            doc.reparse(source).unwrap();
+            assert_eq!(parse_json(source).unwrap(), doc.to_node());
+            assert_eq!(usage, doc.memory_usage());
+        }
+        assert!(doc.reparse("[1,").is_err());
+        assert!(doc.root().is_null());
+        assert_eq!(usage, doc.memory_usage());
+        assert!(Document::parse("[1, 2]").unwrap().memory_usage() < usage);
+    }
+
+    #[test]
+    fn views_outlive_nothing_but_document() {
+        let names: Vec<&str>;
+        let doc = Document::parse(CARDS).unwrap();
+        {
+            let root = doc.root();
+            names = root.elements().filter_map(|card| card.get_string("name").ok()).collect();
+        }
+        assert_eq!(parse_json(CARDS).unwrap().len(), names.len());
+        assert_eq!("Santa", names[0]);
+        assert!(doc.memory_usage() > 0);
+        assert_eq!(Node::Null, Document::parse("null").unwrap().to_node());
+    }
+}
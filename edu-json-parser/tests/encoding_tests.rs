@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{
+        parse_json, parse_json_bytes, parse_json_bytes_with, decode_json_bytes, detect_encoding,
+        Encoding, InvalidInput, BytesOptions, ParseError
+    };
+
+    const SOURCE: &str = r#"{"name": "Säntа 🎅", "sizes": [1, 2]}"#;
+
+    const DETECT: BytesOptions = BytesOptions { detect_encoding: true, invalid: InvalidInput::Fail };
+
+    fn utf16(s: &str, big_endian: bool, bom: bool) -> Vec<u8> {
+        let mut units: Vec<u16> = s.encode_utf16().collect();
+        if bom {
+            units.insert(0, 0xFEFF);
+        }
+        units.iter()
+            .flat_map(|u| if big_endian { u.to_be_bytes() } else { u.to_le_bytes() }.to_vec())
+            .collect()
+    }
+
+    fn utf32(s: &str, big_endian: bool, bom: bool) -> Vec<u8> {
+        let mut units: Vec<u32> = s.chars().map(|c| c as u32).collect();
+        if bom {
+            units.insert(0, 0xFEFF);
+        }
+        units.iter()
+            .flat_map(|u| if big_endian { u.to_be_bytes() } else { u.to_le_bytes() }.to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn utf8_with_and_without_bom() {
+        let expected = parse_json(SOURCE);
+        assert_eq!(expected, parse_json_bytes(SOURCE.as_bytes()));
+        let with_bom = [&[0xEF, 0xBB, 0xBF][..], SOURCE.as_bytes()].concat();
+        assert_eq!(expected, parse_json_bytes(&with_bom));
+        assert!(parse_json(std::str::from_utf8(&with_bom).unwrap()).is_err());
+    }
+
+    #[test]
+    fn utf16_and_utf32() {
+        let expected = parse_json(SOURCE);
+        for bom in [false, true].iter() {
+            for big_endian in [false, true].iter() {
+                let encoded16 = utf16(SOURCE, *big_endian, *bom);
+                let encoded32 = utf32(SOURCE, *big_endian, *bom);
+                assert_eq!(expected, parse_json_bytes_with(&encoded16, DETECT));
+                assert_eq!(expected, parse_json_bytes_with(&encoded32, DETECT));
+                // only when asked
+                assert!(parse_json_bytes(&encoded16).is_err());
+            }
+        }
+        assert_eq!((Encoding::Utf16Le, 0), detect_encoding(&utf16("1", false, false)));
+        assert_eq!((Encoding::Utf32Be, 4), detect_encoding(&utf32("1", true, true)));
+        assert_eq!((Encoding::Utf8, 0), detect_encoding(b"1"));
+    }
+
+    #[test]
+    fn invalid_input_policy() {
+        let broken = b"[\"ab\xFFc\"]";
+        assert_eq!(
+            Err(ParseError { offset: 4, message: "invalid UTF-8".to_string() }),
+            decode_json_bytes(broken, BytesOptions::default())
+        );
+        let replace = BytesOptions { invalid: InvalidInput::Replace, ..BytesOptions::default() };
+        assert_eq!(Some("ab\u{FFFD}c".into()), parse_json_bytes_with(broken, replace).unwrap()[0].as_string());
+
+        // an unpaired surrogate
+        let mut lone = utf16("[\"a\"]", false, false);
+        lone.splice(4..4, vec![0x00, 0xD8]);
+        assert_eq!(Err(4), decode_json_bytes(&lone, DETECT).map_err(|e| e.offset));
+        let replace = BytesOptions { invalid: InvalidInput::Replace, ..DETECT };
+        assert_eq!("[\"\u{FFFD}a\"]", decode_json_bytes(&lone, replace).unwrap());
+        assert!(decode_json_bytes(&[0x31, 0x00, 0x32], DETECT).is_err());
+    }
+}
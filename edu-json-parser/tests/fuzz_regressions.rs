@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{
+        parse_json, parse_json_lenient, parse_json_simd, parse_json_simd_bytes, parse_json_borrowed,
+        parse_json_lazy, parse_json_bytes, parse_json_tolerant, parse_json_stream, parse_json_parallel,
+        parse_json_parallel_with, parse_json_interned, parse_json_spanned, validate_json, CstDocument, Document, JsonParser, Tape,
+        MAX_DEPTH
+    };
+
+    // Inputs found by the targets in `fuzz/`, each of them used to crash some engine
+    fn every_engine(source: &str) -> Vec<bool> {
+        vec![
+            parse_json(source).is_ok(),
+            parse_json_lenient(source).is_ok(),
+            parse_json_simd(source).is_ok(),
+            parse_json_simd_bytes(source.as_bytes()).is_ok(),
+            parse_json_borrowed(source).is_ok(),
+            parse_json_parallel(source).is_ok(),
+            parse_json_parallel_with(source, 4).is_ok(),
+            parse_json_interned(source).is_ok(),
+            parse_json_spanned(source).is_ok(),
+            parse_json_bytes(source.as_bytes()).is_ok(),
+            validate_json(source.as_bytes()).is_ok(),
+            Document::parse(source).is_ok(),
+            CstDocument::parse(source).is_ok(),
+            Tape::parse(source).is_ok(),
+            JsonParser::new().parse(source).is_ok(),
+            parse_json_lazy(source).and_then(|node| node.to_node()).is_ok(),
+            parse_json_tolerant(source).1.is_empty(),
+            parse_json_stream(source).all(|(_, item)| item.is_ok())
+        ]
+    }
+
+    fn nested(open: &str, depth: usize, close: &str) -> String {
+        format!("{}null{}", open.repeat(depth), close.repeat(depth))
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error() {
+        let sources = [
+            "[".repeat(100_000),
+            "[{\"\":".repeat(50_000),
+            nested("[", 100_000, "]"),
+            nested("{\"a\":", 100_000, "}"),
+            nested("[", MAX_DEPTH + 1, "]"),
+            nested("{\"\":", MAX_DEPTH + 1, "}")
+        ];
+        for source in sources.iter() {
+            assert!(every_engine(source).iter().all(|&ok| !ok), "{:.20}", source);
+        }
+    }
+
+    #[test]
+    fn nesting_up_to_max_depth_is_fine() {
+        let sources = [
+            nested("[", MAX_DEPTH, "]"),
+            nested("{\"\":", MAX_DEPTH, "}"),
+            nested("[{\"\":", MAX_DEPTH / 2, "}]")
+        ];
+        for source in sources.iter() {
+            assert!(every_engine(source).iter().all(|&ok| ok), "{:.20}", source);
+        }
+    }
+
+    #[test]
+    fn parallel_elements_are_as_deep_as_in_the_whole_array() {
+        // enough elements to be split between threads
+        let elements = |last: String| {
+            let mut items = vec!["0".to_string(); 63];
+            items.push(last);
+            format!("[{}]", items.join(","))
+        };
+        let too_deep = elements(nested("[", MAX_DEPTH, "]"));
+        assert!(every_engine(&too_deep).iter().all(|&ok| !ok));
+        let deep = elements(nested("[", MAX_DEPTH - 1, "]"));
+        assert!(every_engine(&deep).iter().all(|&ok| ok));
+        assert_eq!(parse_json(&deep), parse_json_parallel_with(&deep, 4));
+    }
+
+    #[test]
+    fn numbers_out_of_range_do_not_panic() {
+        let sources = [
+            "[100000000000000000000]",
+            "-184467440737095516160",
+            "1e99999999999999999999",
+            "[1E-99999999999999999999]",
+            "123456789012345678901234567890.5e400"
+        ];
+        for source in sources.iter() {
+            assert!(every_engine(source).iter().all(|&ok| ok), "{}", source);
+        }
+    }
+
+    #[test]
+    fn broken_escapes_do_not_panic() {
+        let sources = ["\"\\", "[\"\\u", "\"\\uD800\\u\"", "\"\\uD800\\", "{\"\\uDFFF\\uD800\":", "\"\u{10ffff}\\"];
+        for source in sources.iter() {
+            assert!(every_engine(source).iter().all(|&ok| !ok), "{}", source);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, DecodeError, ErrorCause, Node, Parsable};
+
+    const SOURCE: &str = r#"{
+        "name": "Santa", "nick": null, "age": 99,
+        "sizes": [120, 121, 122], "tags": ["a", 1], "address": {"city": "Rovaniemi"},
+        "points": [{"x": 1, "y": 2}, {"x": 3, "y": 4}]
+    }"#;
+
+    #[derive(PartialEq, Debug)]
+    struct Point {
+        x: f64,
+        y: f64
+    }
+
+    impl Parsable for Point {
+        fn parse_node(json: &Node) -> Result<Self, DecodeError> {
+            let x = json.get_number("x")?;
+            let y = json.get_number("y")?;
+            Ok(Point { x, y })
+        }
+    }
+
+    #[test]
+    fn containers() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(3, node.get_array("sizes").unwrap().len());
+        assert_eq!(Some("Rovaniemi"), node.get_object("address").unwrap()["city"].as_string().as_deref());
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("address".to_string(), "array", "object")), node.get_array("address"));
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("sizes".to_string(), "object", "array")), node.get_object("sizes").map(|_| ()));
+        assert_eq!(Err(ErrorCause::FieldNotExist("nope".to_string())), node.get_array("nope"));
+    }
+
+    #[test]
+    fn arrays_of_parsable() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(
+            Ok(vec![Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 }]),
+            node.get_array_of::<Point>("points")
+        );
+        assert_eq!(Ok(vec![]), parse_json(r#"{"points": []}"#).unwrap().get_array_of::<Point>("points"));
+        let error = node.get_array_of::<Point>("sizes").unwrap_err();
+        assert_eq!("/sizes/0", error.pointer);
+        let error = node.get_array_of::<Point>("points").and(node.get_array_of::<f64>("tags")).unwrap_err();
+        assert_eq!(("/tags/0", Some("number"), Some("string")), (error.pointer.as_str(), error.expected, error.found));
+        assert_eq!("/nope", node.get_array_of::<Point>("nope").unwrap_err().pointer);
+    }
+
+    #[test]
+    fn optional_fields() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(Ok(Some("Santa")), node.get_opt_string("name"));
+        assert_eq!(Ok(None), node.get_opt_string("nick"));
+        assert_eq!(Ok(None), node.get_opt_string("nope"));
+        assert_eq!(Ok(Some(99.0)), node.get_opt_number("age"));
+        assert_eq!(Ok(None), node.get_opt_bool("nope"));
+        assert_eq!(Some(2), node.get_opt_array("tags").unwrap().map(|tags| tags.len()));
+        assert_eq!(Ok(None), node.get_opt_object("nick"));
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("age".to_string(), "string", "number")), node.get_opt_string("age"));
+        assert_eq!(Err(ErrorCause::NodeIsNotADictionary), node["sizes"].get_opt_number("age"));
+    }
+
+    #[test]
+    fn defaults() {
+        let node = parse_json(SOURCE).unwrap();
+        let unknown = Node::String("unknown".into());
+        assert_eq!(Ok(&Node::Number(99.0)), node.get_node_or("age", &unknown));
+        assert_eq!(Ok(&unknown), node.get_node_or("nick", &unknown));
+        assert_eq!(Ok("Santa"), node.get_string_or("name", "nobody"));
+        assert_eq!(Ok("nobody"), node.get_string_or("nick", "nobody"));
+        assert_eq!(Ok(42.0), node.get_number_or("weight", 42.0));
+        assert_eq!(Ok(true), node.get_bool_or("kind", true));
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("name".to_string(), "number", "string")), node.get_number_or("name", 0.0));
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, parse_json_borrowed, parse_json_lazy, Document, ErrorCause, Tape};
+
+    const SOURCE: &str = r#"{"age": 99, "weight": 3.7, "debt": -1, "big": 1e20, "huge": 9007199254740992, "exact": 9007199254740991, "name": "Santa"}"#;
+
+    #[test]
+    fn integral_values_in_range() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(Ok(99), node.get_u8("age"));
+        assert_eq!(Ok(99), node.get_i64("age"));
+        assert_eq!(Ok(-1), node.get_i32("debt"));
+        assert_eq!(Ok(9007199254740991), node.get_u64("exact"));
+        assert_eq!(Some(-1), node["debt"].as_i8());
+        assert_eq!(Some(99), node["age"].as_usize());
+        assert_eq!(Some(0), parse_json("-0").unwrap().as_u32());
+        assert_eq!(Some(-9007199254740991), parse_json("-9007199254740991").unwrap().as_i64());
+    }
+
+    #[test]
+    fn rejects_fractions_and_out_of_range_values() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(Err(ErrorCause::NotAnInteger("weight".to_string(), 3.7, "u32")), node.get_u32("weight"));
+        assert_eq!(Err(ErrorCause::NumberOutOfRange("debt".to_string(), -1.0, "u32")), node.get_u32("debt"));
+        assert_eq!(Err(ErrorCause::NumberOutOfRange("big".to_string(), 1e20, "i64")), node.get_i64("big"));
+        assert_eq!(Err(ErrorCause::NumberOutOfRange("huge".to_string(), 9007199254740992.0, "i32")), node.get_i32("huge"));
+        assert_eq!(None, parse_json("9223372036854775808").unwrap().as_i64());
+        assert_eq!(None, parse_json("256").unwrap().as_u8());
+        assert_eq!(None, node["weight"].as_i64());
+        assert_eq!(None, node["name"].as_i64());
+    }
+
+    #[test]
+    fn rejects_values_which_may_have_been_rounded() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(Err(ErrorCause::InexactNumber("huge".to_string(), 9007199254740992.0, "u64")), node.get_u64("huge"));
+        // 2^53 + 1 has no f64 of its own and is parsed as 2^53
+        let above = parse_json(r#"{"id": 9007199254740993}"#).unwrap();
+        assert_eq!(Err(ErrorCause::InexactNumber("id".to_string(), 9007199254740992.0, "i64")), above.get_i64("id"));
+        assert_eq!(None, above["id"].as_u64());
+        // u64::MAX is rounded up to 2^64, which is not a u64, but the text could have been one
+        let max = parse_json(&format!(r#"{{"id": {}}}"#, u64::MAX)).unwrap();
+        assert_eq!(Err(ErrorCause::InexactNumber("id".to_string(), 18446744073709551616.0, "u64")), max.get_u64("id"));
+        assert_eq!(Err(ErrorCause::NumberOutOfRange("id".to_string(), 18446744073709551616.0, "i32")), max.get_i32("id"));
+        assert_eq!(None, parse_json("-9223372036854775808").unwrap().as_i64());
+        assert_eq!(
+            "Field 'huge' holds 9007199254740992, which may have been rounded, so it can't be read as `i64` exactly",
+            node.get_i64("huge").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn wrong_types_and_missing_fields() {
+        let node = parse_json(SOURCE).unwrap();
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("name".to_string(), "u16", "string")), node.get_u16("name"));
+        assert_eq!(Err(ErrorCause::FieldNotExist("nope".to_string())), node.get_i64("nope"));
+        assert_eq!(Err(ErrorCause::NodeIsNotADictionary), node["age"].get_i64("age"));
+        assert_eq!(
+            "Field 'weight' holds 3.7, which is not an integer, so it can't be read as `u32`",
+            node.get_u32("weight").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn every_node_type_has_them() {
+        let node = parse_json(SOURCE).unwrap();
+        let borrowed = parse_json_borrowed(SOURCE).unwrap();
+        let lazy = parse_json_lazy(SOURCE).unwrap();
+        let doc = Document::parse(SOURCE).unwrap();
+        let tape = Tape::parse(SOURCE).unwrap();
+        for key in ["age", "weight", "debt", "big", "huge", "exact", "name", "nope"].iter() {
+            let expected = node.get_i32(key);
+            assert_eq!(expected, borrowed.get_i32(key), "{}", key);
+            assert_eq!(expected, lazy.get_i32(key), "{}", key);
+            assert_eq!(expected, doc.root().get_i32(key), "{}", key);
+            assert_eq!(expected, tape.root().get_i32(key), "{}", key);
+            assert_eq!(node.get_u64(key), tape.root().get_u64(key), "{}", key);
+            assert_eq!(node[*key].as_u64(), borrowed[*key].as_u64(), "{}", key);
+        }
+    }
+}
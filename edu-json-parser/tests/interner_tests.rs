@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, parse_json_interned, parse_json_with_interner, KeyInterner, Node};
+
+    const LONG_KEYS: &str = r#"[
+        {"a_rather_long_key_name_for_sure": 1, "short": 2},
+        {"a_rather_long_key_name_for_sure": 3, "short": 4}
+    ]"#;
+
+    fn long_key(node: &Node) -> *const u8 {
+        node.as_object().unwrap()
+            .keys()
+            .find(|k| k.len() > 22)
+            .unwrap()
+            .as_str()
+            .as_ptr()
+    }
+
+    #[test]
+    fn same_result_as_parse_json() {
+        let sources = [
+            include!("twitter.json"),
+            include!("citm_catalog.json"),
+            include!("pass01.json"),
+            LONG_KEYS, "null", "\"\\u0041\"", "{\"a\": 1, \"a\": 2}"
+        ];
+        for source in sources.iter() {
+            assert_eq!(parse_json(source), parse_json_interned(source));
+        }
+        assert!(parse_json_interned("{\"a\": }").is_err());
+    }
+
+    #[test]
+    fn keys_are_shared_within_a_parse() {
+        let node = parse_json_interned(LONG_KEYS).unwrap();
+        assert_eq!(long_key(&node[0]), long_key(&node[1]));
+
+        let plain = parse_json(LONG_KEYS).unwrap();
+        assert_ne!(long_key(&plain[0]), long_key(&plain[1]));
+    }
+
+    #[test]
+    fn keys_are_shared_across_documents() {
+        let mut interner = KeyInterner::new();
+        let first = parse_json_with_interner(LONG_KEYS, &mut interner).unwrap();
+        let second = parse_json_with_interner(LONG_KEYS, &mut interner).unwrap();
+        assert_eq!(long_key(&first[0]), long_key(&second[1]));
+        assert_eq!(2, interner.len());
+        assert_eq!("a_rather_long_key_name_for_sure".len(), interner.heap_bytes());
+
+        let again = interner.intern("a_rather_long_key_name_for_sure");
+        assert_eq!(long_key(&first[1]), again.as_str().as_ptr());
+        interner.clear();
+        assert!(interner.is_empty());
+    }
+}
@@ -0,0 +1,467 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, str, thread};
+    use edu_json_parser::{
+        parse_json, parse_json_lenient, parse_json_simd, parse_json_borrowed, parse_json_lazy,
+        parse_json_bytes, validate_json, CstDocument, Document, JsonParser, Tape
+    };
+
+    // Files of https://github.com/nst/JSONTestSuite (test_parsing): `y_` must be accepted,
+    // `n_` must be rejected and `i_` are up to the implementation. What every engine does
+    // on the `i_` ones is kept in `i_results.md`, run with `UPDATE_JSON_TEST_SUITE=1` to
+    // regenerate it after a change in behavior
+    const SUITE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/JSONTestSuite/test_parsing/");
+    const REPORT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/JSONTestSuite/i_results.md");
+
+    #[derive(PartialEq, Eq, Copy, Clone, Debug)]
+    enum Outcome {
+        Accepted,
+        Rejected,
+        Panicked
+    }
+
+    fn text(bytes: &[u8], parse: fn(&str) -> bool) -> bool {
+        str::from_utf8(bytes).map(parse).unwrap_or(false)
+    }
+
+    type Engine = (&'static str, fn(&[u8]) -> bool);
+
+    // every engine which follows RFC 8259; lenient parsing only goes to the report
+    const ENGINES: [Engine; 10] = [
+        ("parse_json", |b| text(b, |s| parse_json(s).is_ok())),
+        ("parse_json_simd", |b| text(b, |s| parse_json_simd(s).is_ok())),
+        ("parse_json_borrowed", |b| text(b, |s| parse_json_borrowed(s).is_ok())),
+        ("parse_json_lazy", |b| text(b, |s| parse_json_lazy(s).and_then(|node| node.to_node()).is_ok())),
+        ("Document", |b| text(b, |s| Document::parse(s).is_ok())),
+        ("CstDocument", |b| text(b, |s| CstDocument::parse(s).is_ok())),
+        ("Tape", |b| text(b, |s| Tape::parse(s).is_ok())),
+        ("JsonParser", |b| text(b, |s| JsonParser::new().parse(s).is_ok())),
+        ("validate_json", |b| validate_json(b).is_ok()),
+        ("parse_json_bytes", |b| parse_json_bytes(b).is_ok())
+    ];
+
+    const LENIENT: Engine = ("parse_json_lenient", |b| text(b, |s| parse_json_lenient(s).is_ok()));
+
+    // Recursive engines need more than the default stack on deeply nested files
+    const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+    fn run(engine: fn(&[u8]) -> bool, bytes: &[u8]) -> Outcome {
+        let bytes = bytes.to_vec();
+        let worker = thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn(move || engine(&bytes))
+            .unwrap();
+        match worker.join() {
+            Ok(true) => Outcome::Accepted,
+            Ok(false) => Outcome::Rejected,
+            Err(_) => Outcome::Panicked
+        }
+    }
+
+    fn read(file: &str) -> Vec<u8> {
+        fs::read(format!("{}{}", SUITE, file)).unwrap()
+    }
+
+    fn check(file: &str) {
+        let bytes = read(file);
+        let expected = match &file[..2] {
+            "y_" => Some(Outcome::Accepted),
+            "n_" => Some(Outcome::Rejected),
+            _ => None
+        };
+        // `i_` files only have to be gone through, outcomes are compared with the report
+        for (name, engine) in ENGINES.iter() {
+            let outcome = run(*engine, &bytes);
+            if let Some(expected) = expected {
+                assert_eq!(expected, outcome, "{} on {}", name, file);
+            }
+        }
+    }
+
+    macro_rules! suite {
+        ($($name:ident: $file:expr),*) => {
+            const FILES: &[&str] = &[$($file),*];
+            $(
+                #[test]
+                fn $name() {
+                    check($file);
+                }
+            )*
+        }
+    }
+
+    suite! {
+        i_number_double_huge_neg_exp: "i_number_double_huge_neg_exp.json",
+        i_number_huge_exp: "i_number_huge_exp.json",
+        i_number_neg_int_huge_exp: "i_number_neg_int_huge_exp.json",
+        i_number_pos_double_huge_exp: "i_number_pos_double_huge_exp.json",
+        i_number_real_neg_overflow: "i_number_real_neg_overflow.json",
+        i_number_real_pos_overflow: "i_number_real_pos_overflow.json",
+        i_number_real_underflow: "i_number_real_underflow.json",
+        i_number_too_big_neg_int: "i_number_too_big_neg_int.json",
+        i_number_too_big_pos_int: "i_number_too_big_pos_int.json",
+        i_number_very_big_negative_int: "i_number_very_big_negative_int.json",
+        i_object_key_lone_2nd_surrogate: "i_object_key_lone_2nd_surrogate.json",
+        i_string_1st_surrogate_but_2nd_missing: "i_string_1st_surrogate_but_2nd_missing.json",
+        i_string_1st_valid_surrogate_2nd_invalid: "i_string_1st_valid_surrogate_2nd_invalid.json",
+        i_string_utf_16le_with_bom: "i_string_UTF-16LE_with_BOM.json",
+        i_string_utf_8_invalid_sequence: "i_string_UTF-8_invalid_sequence.json",
+        i_string_utf8_surrogate_uplus_d800: "i_string_UTF8_surrogate_U+D800.json",
+        i_string_incomplete_surrogate_and_escape_valid: "i_string_incomplete_surrogate_and_escape_valid.json",
+        i_string_incomplete_surrogate_pair: "i_string_incomplete_surrogate_pair.json",
+        i_string_incomplete_surrogates_escape_valid: "i_string_incomplete_surrogates_escape_valid.json",
+        i_string_invalid_lonely_surrogate: "i_string_invalid_lonely_surrogate.json",
+        i_string_invalid_surrogate: "i_string_invalid_surrogate.json",
+        i_string_invalid_utf_8: "i_string_invalid_utf-8.json",
+        i_string_inverted_surrogates_uplus_1d11e: "i_string_inverted_surrogates_U+1D11E.json",
+        i_string_iso_latin_1: "i_string_iso_latin_1.json",
+        i_string_lone_second_surrogate: "i_string_lone_second_surrogate.json",
+        i_string_lone_utf8_continuation_byte: "i_string_lone_utf8_continuation_byte.json",
+        i_string_not_in_unicode_range: "i_string_not_in_unicode_range.json",
+        i_string_overlong_sequence_2_bytes: "i_string_overlong_sequence_2_bytes.json",
+        i_string_overlong_sequence_6_bytes: "i_string_overlong_sequence_6_bytes.json",
+        i_string_overlong_sequence_6_bytes_null: "i_string_overlong_sequence_6_bytes_null.json",
+        i_string_truncated_utf_8: "i_string_truncated-utf-8.json",
+        i_string_utf16be_no_bom: "i_string_utf16BE_no_BOM.json",
+        i_string_utf16le_no_bom: "i_string_utf16LE_no_BOM.json",
+        i_structure_500_nested_arrays: "i_structure_500_nested_arrays.json",
+        i_structure_utf_8_bom_empty_object: "i_structure_UTF-8_BOM_empty_object.json",
+        n_array_1_true_without_comma: "n_array_1_true_without_comma.json",
+        n_array_a_invalid_utf8: "n_array_a_invalid_utf8.json",
+        n_array_colon_instead_of_comma: "n_array_colon_instead_of_comma.json",
+        n_array_comma_after_close: "n_array_comma_after_close.json",
+        n_array_comma_and_number: "n_array_comma_and_number.json",
+        n_array_double_comma: "n_array_double_comma.json",
+        n_array_double_extra_comma: "n_array_double_extra_comma.json",
+        n_array_extra_close: "n_array_extra_close.json",
+        n_array_extra_comma: "n_array_extra_comma.json",
+        n_array_incomplete: "n_array_incomplete.json",
+        n_array_incomplete_invalid_value: "n_array_incomplete_invalid_value.json",
+        n_array_inner_array_no_comma: "n_array_inner_array_no_comma.json",
+        n_array_invalid_utf8: "n_array_invalid_utf8.json",
+        n_array_items_separated_by_semicolon: "n_array_items_separated_by_semicolon.json",
+        n_array_just_comma: "n_array_just_comma.json",
+        n_array_just_minus: "n_array_just_minus.json",
+        n_array_missing_value: "n_array_missing_value.json",
+        n_array_newlines_unclosed: "n_array_newlines_unclosed.json",
+        n_array_number_and_comma: "n_array_number_and_comma.json",
+        n_array_number_and_several_commas: "n_array_number_and_several_commas.json",
+        n_array_spaces_vertical_tab_formfeed: "n_array_spaces_vertical_tab_formfeed.json",
+        n_array_star_inside: "n_array_star_inside.json",
+        n_array_unclosed: "n_array_unclosed.json",
+        n_array_unclosed_trailing_comma: "n_array_unclosed_trailing_comma.json",
+        n_array_unclosed_with_new_lines: "n_array_unclosed_with_new_lines.json",
+        n_array_unclosed_with_object_inside: "n_array_unclosed_with_object_inside.json",
+        n_incomplete_false: "n_incomplete_false.json",
+        n_incomplete_null: "n_incomplete_null.json",
+        n_incomplete_true: "n_incomplete_true.json",
+        n_multidigit_number_then_00: "n_multidigit_number_then_00.json",
+        n_number_plus_plus: "n_number_++.json",
+        n_number_plus_1: "n_number_+1.json",
+        n_number_plus_inf: "n_number_+Inf.json",
+        n_number_01: "n_number_-01.json",
+        n_number_1_dot_0_dot: "n_number_-1.0..json",
+        n_number_2_dot: "n_number_-2..json",
+        n_number_minus_nan: "n_number_-NaN.json",
+        n_number_dot_1: "n_number_.-1.json",
+        n_number_dot_2e_3: "n_number_.2e-3.json",
+        n_number_0_dot_1_dot_2: "n_number_0.1.2.json",
+        n_number_0_dot_3eplus: "n_number_0.3e+.json",
+        n_number_0_dot_3e: "n_number_0.3e.json",
+        n_number_0_dot_e1: "n_number_0.e1.json",
+        n_number_0_capital_eplus: "n_number_0_capital_E+.json",
+        n_number_0_capital_e: "n_number_0_capital_E.json",
+        n_number_0eplus: "n_number_0e+.json",
+        n_number_0e: "n_number_0e.json",
+        n_number_1_dot_0eplus: "n_number_1.0e+.json",
+        n_number_1_dot_0eminus: "n_number_1.0e-.json",
+        n_number_1_dot_0e: "n_number_1.0e.json",
+        n_number_1_000: "n_number_1_000.json",
+        n_number_1ee2: "n_number_1eE2.json",
+        n_number_2_dot_eplus_3: "n_number_2.e+3.json",
+        n_number_2_dot_e_3: "n_number_2.e-3.json",
+        n_number_2_dot_e3: "n_number_2.e3.json",
+        n_number_9_dot_eplus: "n_number_9.e+.json",
+        n_number_inf: "n_number_Inf.json",
+        n_number_nan: "n_number_NaN.json",
+        n_number_uplus_ff11_fullwidth_digit_one: "n_number_U+FF11_fullwidth_digit_one.json",
+        n_number_expression: "n_number_expression.json",
+        n_number_hex_1_digit: "n_number_hex_1_digit.json",
+        n_number_hex_2_digits: "n_number_hex_2_digits.json",
+        n_number_infinity: "n_number_infinity.json",
+        n_number_invalidplus: "n_number_invalid+-.json",
+        n_number_invalid_negative_real: "n_number_invalid-negative-real.json",
+        n_number_invalid_utf_8_in_bigger_int: "n_number_invalid-utf-8-in-bigger-int.json",
+        n_number_invalid_utf_8_in_exponent: "n_number_invalid-utf-8-in-exponent.json",
+        n_number_invalid_utf_8_in_int: "n_number_invalid-utf-8-in-int.json",
+        n_number_minus_infinity: "n_number_minus_infinity.json",
+        n_number_minus_sign_with_trailing_garbage: "n_number_minus_sign_with_trailing_garbage.json",
+        n_number_minus_space_1: "n_number_minus_space_1.json",
+        n_number_neg_int_starting_with_zero: "n_number_neg_int_starting_with_zero.json",
+        n_number_neg_real_without_int_part: "n_number_neg_real_without_int_part.json",
+        n_number_neg_with_garbage_at_end: "n_number_neg_with_garbage_at_end.json",
+        n_number_real_garbage_after_e: "n_number_real_garbage_after_e.json",
+        n_number_real_with_invalid_utf8_after_e: "n_number_real_with_invalid_utf8_after_e.json",
+        n_number_real_without_fractional_part: "n_number_real_without_fractional_part.json",
+        n_number_starting_with_dot: "n_number_starting_with_dot.json",
+        n_number_with_alpha: "n_number_with_alpha.json",
+        n_number_with_alpha_char: "n_number_with_alpha_char.json",
+        n_number_with_leading_zero: "n_number_with_leading_zero.json",
+        n_object_bad_value: "n_object_bad_value.json",
+        n_object_bracket_key: "n_object_bracket_key.json",
+        n_object_comma_instead_of_colon: "n_object_comma_instead_of_colon.json",
+        n_object_double_colon: "n_object_double_colon.json",
+        n_object_emoji: "n_object_emoji.json",
+        n_object_garbage_at_end: "n_object_garbage_at_end.json",
+        n_object_key_with_single_quotes: "n_object_key_with_single_quotes.json",
+        n_object_lone_continuation_byte_in_key_and_trailing_comma: "n_object_lone_continuation_byte_in_key_and_trailing_comma.json",
+        n_object_missing_colon: "n_object_missing_colon.json",
+        n_object_missing_key: "n_object_missing_key.json",
+        n_object_missing_semicolon: "n_object_missing_semicolon.json",
+        n_object_missing_value: "n_object_missing_value.json",
+        n_object_no_colon: "n_object_no-colon.json",
+        n_object_non_string_key: "n_object_non_string_key.json",
+        n_object_non_string_key_but_huge_number_instead: "n_object_non_string_key_but_huge_number_instead.json",
+        n_object_repeated_null_null: "n_object_repeated_null_null.json",
+        n_object_several_trailing_commas: "n_object_several_trailing_commas.json",
+        n_object_single_quote: "n_object_single_quote.json",
+        n_object_trailing_comma: "n_object_trailing_comma.json",
+        n_object_trailing_comment: "n_object_trailing_comment.json",
+        n_object_trailing_comment_open: "n_object_trailing_comment_open.json",
+        n_object_trailing_comment_slash_open: "n_object_trailing_comment_slash_open.json",
+        n_object_trailing_comment_slash_open_incomplete: "n_object_trailing_comment_slash_open_incomplete.json",
+        n_object_two_commas_in_a_row: "n_object_two_commas_in_a_row.json",
+        n_object_unquoted_key: "n_object_unquoted_key.json",
+        n_object_unterminated_value: "n_object_unterminated-value.json",
+        n_object_with_single_string: "n_object_with_single_string.json",
+        n_object_with_trailing_garbage: "n_object_with_trailing_garbage.json",
+        n_single_space: "n_single_space.json",
+        n_string_1_surrogate_then_escape: "n_string_1_surrogate_then_escape.json",
+        n_string_1_surrogate_then_escape_u: "n_string_1_surrogate_then_escape_u.json",
+        n_string_1_surrogate_then_escape_u1: "n_string_1_surrogate_then_escape_u1.json",
+        n_string_1_surrogate_then_escape_u1x: "n_string_1_surrogate_then_escape_u1x.json",
+        n_string_accentuated_char_no_quotes: "n_string_accentuated_char_no_quotes.json",
+        n_string_backslash_00: "n_string_backslash_00.json",
+        n_string_escape_x: "n_string_escape_x.json",
+        n_string_escaped_backslash_bad: "n_string_escaped_backslash_bad.json",
+        n_string_escaped_ctrl_char_tab: "n_string_escaped_ctrl_char_tab.json",
+        n_string_escaped_emoji: "n_string_escaped_emoji.json",
+        n_string_incomplete_escape: "n_string_incomplete_escape.json",
+        n_string_incomplete_escaped_character: "n_string_incomplete_escaped_character.json",
+        n_string_incomplete_surrogate: "n_string_incomplete_surrogate.json",
+        n_string_incomplete_surrogate_escape_invalid: "n_string_incomplete_surrogate_escape_invalid.json",
+        n_string_invalid_utf_8_in_escape: "n_string_invalid-utf-8-in-escape.json",
+        n_string_invalid_backslash_esc: "n_string_invalid_backslash_esc.json",
+        n_string_invalid_unicode_escape: "n_string_invalid_unicode_escape.json",
+        n_string_invalid_utf8_after_escape: "n_string_invalid_utf8_after_escape.json",
+        n_string_leading_uescaped_thinspace: "n_string_leading_uescaped_thinspace.json",
+        n_string_no_quotes_with_bad_escape: "n_string_no_quotes_with_bad_escape.json",
+        n_string_single_doublequote: "n_string_single_doublequote.json",
+        n_string_single_quote: "n_string_single_quote.json",
+        n_string_single_string_no_double_quotes: "n_string_single_string_no_double_quotes.json",
+        n_string_start_escape_unclosed: "n_string_start_escape_unclosed.json",
+        n_string_unescaped_ctrl_char: "n_string_unescaped_ctrl_char.json",
+        n_string_unescaped_newline: "n_string_unescaped_newline.json",
+        n_string_unescaped_tab: "n_string_unescaped_tab.json",
+        n_string_unicode_capitalu: "n_string_unicode_CapitalU.json",
+        n_string_with_trailing_garbage: "n_string_with_trailing_garbage.json",
+        n_structure_100000_opening_arrays: "n_structure_100000_opening_arrays.json",
+        n_structure_uplus_2060_word_joined: "n_structure_U+2060_word_joined.json",
+        n_structure_utf8_bom_no_data: "n_structure_UTF8_BOM_no_data.json",
+        n_structure_angle_bracket_dot: "n_structure_angle_bracket_..json",
+        n_structure_angle_bracket_null: "n_structure_angle_bracket_null.json",
+        n_structure_array_trailing_garbage: "n_structure_array_trailing_garbage.json",
+        n_structure_array_with_extra_array_close: "n_structure_array_with_extra_array_close.json",
+        n_structure_array_with_unclosed_string: "n_structure_array_with_unclosed_string.json",
+        n_structure_ascii_unicode_identifier: "n_structure_ascii-unicode-identifier.json",
+        n_structure_capitalized_true: "n_structure_capitalized_True.json",
+        n_structure_close_unopened_array: "n_structure_close_unopened_array.json",
+        n_structure_comma_instead_of_closing_brace: "n_structure_comma_instead_of_closing_brace.json",
+        n_structure_double_array: "n_structure_double_array.json",
+        n_structure_end_array: "n_structure_end_array.json",
+        n_structure_incomplete_utf8_bom: "n_structure_incomplete_UTF8_BOM.json",
+        n_structure_lone_invalid_utf_8: "n_structure_lone-invalid-utf-8.json",
+        n_structure_lone_open_bracket: "n_structure_lone-open-bracket.json",
+        n_structure_no_data: "n_structure_no_data.json",
+        n_structure_null_byte_outside_string: "n_structure_null-byte-outside-string.json",
+        n_structure_number_with_trailing_garbage: "n_structure_number_with_trailing_garbage.json",
+        n_structure_object_followed_by_closing_object: "n_structure_object_followed_by_closing_object.json",
+        n_structure_object_unclosed_no_value: "n_structure_object_unclosed_no_value.json",
+        n_structure_object_with_comment: "n_structure_object_with_comment.json",
+        n_structure_object_with_trailing_garbage: "n_structure_object_with_trailing_garbage.json",
+        n_structure_open_array_apostrophe: "n_structure_open_array_apostrophe.json",
+        n_structure_open_array_comma: "n_structure_open_array_comma.json",
+        n_structure_open_array_object: "n_structure_open_array_object.json",
+        n_structure_open_array_open_object: "n_structure_open_array_open_object.json",
+        n_structure_open_array_open_string: "n_structure_open_array_open_string.json",
+        n_structure_open_array_string: "n_structure_open_array_string.json",
+        n_structure_open_object: "n_structure_open_object.json",
+        n_structure_open_object_close_array: "n_structure_open_object_close_array.json",
+        n_structure_open_object_comma: "n_structure_open_object_comma.json",
+        n_structure_open_object_open_array: "n_structure_open_object_open_array.json",
+        n_structure_open_object_open_string: "n_structure_open_object_open_string.json",
+        n_structure_open_object_string_with_apostrophes: "n_structure_open_object_string_with_apostrophes.json",
+        n_structure_open_open: "n_structure_open_open.json",
+        n_structure_single_eacute: "n_structure_single_eacute.json",
+        n_structure_single_star: "n_structure_single_star.json",
+        n_structure_trailing_hash: "n_structure_trailing_#.json",
+        n_structure_uescaped_lf_before_string: "n_structure_uescaped_LF_before_string.json",
+        n_structure_unclosed_array: "n_structure_unclosed_array.json",
+        n_structure_unclosed_array_partial_null: "n_structure_unclosed_array_partial_null.json",
+        n_structure_unclosed_array_unfinished_false: "n_structure_unclosed_array_unfinished_false.json",
+        n_structure_unclosed_array_unfinished_true: "n_structure_unclosed_array_unfinished_true.json",
+        n_structure_unclosed_object: "n_structure_unclosed_object.json",
+        n_structure_unicode_identifier: "n_structure_unicode-identifier.json",
+        n_structure_whitespace_uplus_2060_word_joiner: "n_structure_whitespace_U+2060_word_joiner.json",
+        n_structure_whitespace_formfeed: "n_structure_whitespace_formfeed.json",
+        y_array_arrayswithspaces: "y_array_arraysWithSpaces.json",
+        y_array_empty_string: "y_array_empty-string.json",
+        y_array_empty: "y_array_empty.json",
+        y_array_ending_with_newline: "y_array_ending_with_newline.json",
+        y_array_false: "y_array_false.json",
+        y_array_heterogeneous: "y_array_heterogeneous.json",
+        y_array_null: "y_array_null.json",
+        y_array_with_1_and_newline: "y_array_with_1_and_newline.json",
+        y_array_with_leading_space: "y_array_with_leading_space.json",
+        y_array_with_several_null: "y_array_with_several_null.json",
+        y_array_with_trailing_space: "y_array_with_trailing_space.json",
+        y_number: "y_number.json",
+        y_number_0eplus_1: "y_number_0e+1.json",
+        y_number_0e1: "y_number_0e1.json",
+        y_number_after_space: "y_number_after_space.json",
+        y_number_double_close_to_zero: "y_number_double_close_to_zero.json",
+        y_number_int_with_exp: "y_number_int_with_exp.json",
+        y_number_minus_zero: "y_number_minus_zero.json",
+        y_number_negative_int: "y_number_negative_int.json",
+        y_number_negative_one: "y_number_negative_one.json",
+        y_number_negative_zero: "y_number_negative_zero.json",
+        y_number_real_capital_e: "y_number_real_capital_e.json",
+        y_number_real_capital_e_neg_exp: "y_number_real_capital_e_neg_exp.json",
+        y_number_real_capital_e_pos_exp: "y_number_real_capital_e_pos_exp.json",
+        y_number_real_exponent: "y_number_real_exponent.json",
+        y_number_real_fraction_exponent: "y_number_real_fraction_exponent.json",
+        y_number_real_neg_exp: "y_number_real_neg_exp.json",
+        y_number_real_pos_exponent: "y_number_real_pos_exponent.json",
+        y_number_simple_int: "y_number_simple_int.json",
+        y_number_simple_real: "y_number_simple_real.json",
+        y_object: "y_object.json",
+        y_object_basic: "y_object_basic.json",
+        y_object_duplicated_key: "y_object_duplicated_key.json",
+        y_object_duplicated_key_and_value: "y_object_duplicated_key_and_value.json",
+        y_object_empty: "y_object_empty.json",
+        y_object_empty_key: "y_object_empty_key.json",
+        y_object_escaped_null_in_key: "y_object_escaped_null_in_key.json",
+        y_object_extreme_numbers: "y_object_extreme_numbers.json",
+        y_object_long_strings: "y_object_long_strings.json",
+        y_object_simple: "y_object_simple.json",
+        y_object_string_unicode: "y_object_string_unicode.json",
+        y_object_with_newlines: "y_object_with_newlines.json",
+        y_string_1_2_3_bytes_utf_8_sequences: "y_string_1_2_3_bytes_UTF-8_sequences.json",
+        y_string_accepted_surrogate_pair: "y_string_accepted_surrogate_pair.json",
+        y_string_accepted_surrogate_pairs: "y_string_accepted_surrogate_pairs.json",
+        y_string_allowed_escapes: "y_string_allowed_escapes.json",
+        y_string_backslash_and_u_escaped_zero: "y_string_backslash_and_u_escaped_zero.json",
+        y_string_backslash_doublequotes: "y_string_backslash_doublequotes.json",
+        y_string_comments: "y_string_comments.json",
+        y_string_double_escape_a: "y_string_double_escape_a.json",
+        y_string_double_escape_n: "y_string_double_escape_n.json",
+        y_string_escaped_control_character: "y_string_escaped_control_character.json",
+        y_string_escaped_noncharacter: "y_string_escaped_noncharacter.json",
+        y_string_in_array: "y_string_in_array.json",
+        y_string_in_array_with_leading_space: "y_string_in_array_with_leading_space.json",
+        y_string_last_surrogates_1_and_2: "y_string_last_surrogates_1_and_2.json",
+        y_string_nbsp_uescaped: "y_string_nbsp_uescaped.json",
+        y_string_noncharacterinutf_8_uplus_10ffff: "y_string_nonCharacterInUTF-8_U+10FFFF.json",
+        y_string_noncharacterinutf_8_uplus_ffff: "y_string_nonCharacterInUTF-8_U+FFFF.json",
+        y_string_null_escape: "y_string_null_escape.json",
+        y_string_one_byte_utf_8: "y_string_one-byte-utf-8.json",
+        y_string_pi: "y_string_pi.json",
+        y_string_reservedcharacterinutf_8_uplus_1bfff: "y_string_reservedCharacterInUTF-8_U+1BFFF.json",
+        y_string_simple_ascii: "y_string_simple_ascii.json",
+        y_string_space: "y_string_space.json",
+        y_string_surrogates_uplus_1d11e_musical_symbol_g_clef: "y_string_surrogates_U+1D11E_MUSICAL_SYMBOL_G_CLEF.json",
+        y_string_three_byte_utf_8: "y_string_three-byte-utf-8.json",
+        y_string_two_byte_utf_8: "y_string_two-byte-utf-8.json",
+        y_string_uplus_2028_line_sep: "y_string_u+2028_line_sep.json",
+        y_string_uplus_2029_par_sep: "y_string_u+2029_par_sep.json",
+        y_string_uescape: "y_string_uEscape.json",
+        y_string_uescaped_newline: "y_string_uescaped_newline.json",
+        y_string_unescaped_char_delete: "y_string_unescaped_char_delete.json",
+        y_string_unicode: "y_string_unicode.json",
+        y_string_unicodeescapedbackslash: "y_string_unicodeEscapedBackslash.json",
+        y_string_unicode_2: "y_string_unicode_2.json",
+        y_string_unicode_uplus_10fffe_nonchar: "y_string_unicode_U+10FFFE_nonchar.json",
+        y_string_unicode_uplus_1fffe_nonchar: "y_string_unicode_U+1FFFE_nonchar.json",
+        y_string_unicode_uplus_200b_zero_width_space: "y_string_unicode_U+200B_ZERO_WIDTH_SPACE.json",
+        y_string_unicode_uplus_2064_invisible_plus: "y_string_unicode_U+2064_invisible_plus.json",
+        y_string_unicode_uplus_fdd0_nonchar: "y_string_unicode_U+FDD0_nonchar.json",
+        y_string_unicode_uplus_fffe_nonchar: "y_string_unicode_U+FFFE_nonchar.json",
+        y_string_unicode_escaped_double_quote: "y_string_unicode_escaped_double_quote.json",
+        y_string_utf8: "y_string_utf8.json",
+        y_string_with_del_character: "y_string_with_del_character.json",
+        y_structure_lonely_false: "y_structure_lonely_false.json",
+        y_structure_lonely_int: "y_structure_lonely_int.json",
+        y_structure_lonely_negative_real: "y_structure_lonely_negative_real.json",
+        y_structure_lonely_null: "y_structure_lonely_null.json",
+        y_structure_lonely_string: "y_structure_lonely_string.json",
+        y_structure_lonely_true: "y_structure_lonely_true.json",
+        y_structure_string_empty: "y_structure_string_empty.json",
+        y_structure_trailing_newline: "y_structure_trailing_newline.json",
+        y_structure_true_in_array: "y_structure_true_in_array.json",
+        y_structure_whitespace_array: "y_structure_whitespace_array.json"
+    }
+
+    #[test]
+    fn every_file_is_listed() {
+        let mut on_disk: Vec<String> = fs::read_dir(SUITE).unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        on_disk.sort();
+        let mut listed: Vec<&str> = FILES.to_vec();
+        listed.sort();
+        assert_eq!(listed, on_disk);
+    }
+
+    fn cell(outcome: Outcome) -> &'static str {
+        match outcome {
+            Outcome::Accepted => "accepted",
+            Outcome::Rejected => "rejected",
+            Outcome::Panicked => "panicked"
+        }
+    }
+
+    fn report() -> String {
+        let engines: Vec<_> = ENGINES.iter().chain(Some(&LENIENT)).collect();
+        let mut out = String::from(
+            "# JSONTestSuite `i_` results\n\n\
+            Generated by `tests/json_test_suite.rs`. `panicked` stands for a debug build panic,\n\
+            like an integer overflow\n\n| file |"
+        );
+        for (name, _) in engines.iter() {
+            out.push_str(&format!(" {} |", name));
+        }
+        out.push_str("\n|---|");
+        out.push_str(&"---|".repeat(engines.len()));
+        out.push('\n');
+        let mut files: Vec<&str> = FILES.iter().cloned().filter(|file| file.starts_with("i_")).collect();
+        files.sort();
+        for file in files {
+            let bytes = read(file);
+            out.push_str(&format!("| {} |", file));
+            for (_, engine) in engines.iter() {
+                out.push_str(&format!(" {} |", cell(run(*engine, &bytes))));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    #[test]
+    fn implementation_defined_results_are_recorded() {
+        let actual = report();
+        if std::env::var_os("UPDATE_JSON_TEST_SUITE").is_some() {
+            fs::write(REPORT, &actual).unwrap();
+        }
+        assert_eq!(fs::read_to_string(REPORT).unwrap(), actual);
+    }
+}
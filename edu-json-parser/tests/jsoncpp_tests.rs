@@ -1,9 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use edu_json_parser::{parse_json};
+    use edu_json_parser::{parse_json, parse_json_with, ParseOptions};
 
     const FAILS: [&'static str; 33] = [
-        include!("fail01.json"), // <- arguable case so commented in asserts
+        include!("fail01.json"), // <- arguable case, see ARGUABLE
         include!("fail02.json"),
         include!("fail03.json"),
         include!("fail04.json"),
@@ -20,7 +20,7 @@ mod tests {
         include!("fail15.json"),
         include!("fail16.json"),
         include!("fail17.json"),
-        include!("fail18.json"), // <- arguable case so commented in asserts
+        include!("fail18.json"), // <- arguable case, see ARGUABLE
         include!("fail19.json"),
         include!("fail20.json"),
         include!("fail21.json"),
@@ -44,10 +44,24 @@ mod tests {
         include!("pass03.json")
     ];
 
+    // fail01 (a lone string) and fail18 (20 nested arrays) come from RFC 4627 times: RFC 8259
+    // allows any value at the top level and leaves the nesting limit to an implementation.
+    // `parse_json` accepts both, `ParseOptions` can bring the old rules back
+    #[test]
+    fn json_cpp_arguable() {
+        assert!(parse_json(FAILS[0]).is_ok());
+        assert!(parse_json(FAILS[17]).is_ok());
+        let jsoncpp = ParseOptions { containers_only: true, max_depth: 19 };
+        assert!(parse_json_with(FAILS[0], jsoncpp).is_err());
+        assert!(parse_json_with(FAILS[17], jsoncpp).is_err());
+        for pass in PASSES.iter() {
+            assert!(parse_json_with(pass, jsoncpp).is_ok());
+        }
+        assert!(parse_json_with(FAILS[17], ParseOptions { max_depth: 20, ..ParseOptions::default() }).is_ok());
+    }
+
     #[test]
     fn json_cpp_fails() {
-        //assert!(parse_json(FAILS[0]).is_err());
-        //^^^ arguable case
         assert!(parse_json(FAILS[1]).is_err());
         assert!(parse_json(FAILS[2]).is_err());
         assert!(parse_json(FAILS[3]).is_err());
@@ -64,8 +78,6 @@ mod tests {
         assert!(parse_json(FAILS[14]).is_err());
         assert!(parse_json(FAILS[15]).is_err());
         assert!(parse_json(FAILS[16]).is_err());
-        //assert!(parse_json(FAILS[17]).is_err());
-        //^^^ arguable case
         assert!(parse_json(FAILS[18]).is_err());
         assert!(parse_json(FAILS[19]).is_err());
         assert!(parse_json(FAILS[20]).is_err());
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, parse_json_lazy, ErrorCause, Node};
+
+    const TWITTER: &str = include!("twitter.json");
+
+    #[test]
+    fn two_fields_out_of_a_big_document() {
+        let lazy = parse_json_lazy(TWITTER).unwrap();
+        let node = parse_json(TWITTER).unwrap();
+        let metadata = &lazy["search_metadata"];
+        assert_eq!(
+            node["search_metadata"].get_number("count"),
+            metadata.get_number("count")
+        );
+        assert_eq!(
+            node["statuses"][3]["user"].get_string("screen_name").map(|s| s.to_string()),
+            lazy["statuses"][3]["user"].get_string("screen_name").map(|s| s.to_string())
+        );
+        assert_eq!(node["statuses"].len(), lazy["statuses"].len());
+        assert_eq!(Ok(node.clone()), lazy.to_node());
+    }
+
+    #[test]
+    fn malformed_scalars() {
+        assert_eq!(Err("invalid value at byte 1".to_string()), parse_json_lazy(" xyz ").map(|n| n.raw()));
+        assert!(parse_json_lazy("1x").is_err());
+        assert!(parse_json_lazy(r#""\q""#).is_err());
+        assert_eq!(Some(15.0), parse_json_lazy(" 1.5e1 ").unwrap().as_number());
+
+        let lazy = parse_json_lazy(r#"{"a": 1x, "b": "\q", "c": tru, "d": 1.50}"#).unwrap();
+        assert_eq!(vec!["invalid", "invalid", "invalid", "number"], lazy.entries().map(|(_, v)| v.kind()).collect::<Vec<_>>());
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("a".to_string(), "number", "invalid")), lazy.get_number("a"));
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("a".to_string(), "u8", "invalid")), lazy.get_u8("a"));
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("b".to_string(), "string", "invalid")), lazy.get_string("b"));
+        assert_eq!(Err(ErrorCause::WrongTypeRequested("c".to_string(), "bool", "invalid")), lazy.get_bool("c"));
+        assert_eq!(Ok("1.5".to_string()), lazy.get_as_string("d"));
+        assert_eq!("1.50", lazy["d"].raw());
+    }
+
+    #[test]
+    fn unvisited_parts_are_only_skimmed() {
+        assert!(parse_json_lazy("[1, 2").is_err());
+        assert!(parse_json_lazy("{\"a\": [}").is_err());
+        assert!(parse_json_lazy("[\"]\"] x").is_err());
+
+        // a broken scalar deep inside goes unnoticed until someone looks at it
+        let lazy = parse_json_lazy(r#"{"ok": 1, "deep": [[tru]]}"#).unwrap();
+        assert_eq!(Ok(1.0), lazy.get_number("ok"));
+        assert!(!lazy["deep"][0][0].is_bool());
+        assert!(lazy.to_node().is_err());
+
+        // nor is a container nobody has opened
+        let lazy = parse_json_lazy(r#"{"sizes": [120 121], "dup": 1, "dup": 2}"#).unwrap();
+        assert_eq!(Ok(2.0), lazy.get_number("dup"));
+        assert_eq!(vec!["sizes", "dup", "dup"], lazy.entries().map(|(k, _)| k).collect::<Vec<_>>());
+        assert_eq!(None, lazy["sizes"].try_len());
+        match lazy["sizes"].get_element_at(0) {
+            Err(ErrorCause::MalformedJson(_)) => {},
+            other => panic!("unexpected {:?}", other.map(|n| n.raw()))
+        }
+
+        let broken = parse_json_lazy("{\"a\" 1}").unwrap();
+        match broken.get("a") {
+            Err(ErrorCause::MalformedJson(_)) => {},
+            other => panic!("unexpected {:?}", other.map(|n| n.raw()))
+        }
+        assert_eq!(Ok(Node::Null), parse_json_lazy(" null ").unwrap().to_node());
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, parse_json_parallel, parse_json_parallel_with, parse_json_lines_parallel_with};
+
+    fn big_array(n: usize) -> String {
+        let items: Vec<String> = (0..n)
+            .map(|i| format!(r#"{{"id": {}, "tags": ["a", "b\"]"], "nested": [[{}], {{}}]}}"#, i, i * 2))
+            .collect();
+        format!(" [\n{}\n] ", items.join(",\n"))
+    }
+
+    #[test]
+    fn same_result_as_parse_json() {
+        let big = big_array(1000);
+        let sources = [
+            big.as_str(), include!("twitter.json"), include!("canada.json"), include!("pass01.json"),
+            "[]", " [ ] ", "[1]", "{}", "null"
+        ];
+        for source in sources.iter() {
+            for threads in [1, 2, 7].iter() {
+                assert_eq!(parse_json(source), parse_json_parallel_with(source, *threads));
+            }
+            assert_eq!(parse_json(source), parse_json_parallel(source));
+        }
+    }
+
+    #[test]
+    fn errors_are_the_same_as_well() {
+        let big = big_array(500);
+        let broken_element = big.replacen("\"id\": 250", "\"id\": 2 50", 1);
+        let broken_tail = format!("{} x", big);
+        let unbalanced = big.replacen("[[", "[[[", 1);
+        for source in [broken_element, broken_tail, unbalanced].iter() {
+            let expected = parse_json(source);
+            assert!(expected.is_err());
+            assert_eq!(expected, parse_json_parallel_with(source, 4));
+        }
+    }
+
+    #[test]
+    fn json_lines() {
+        let mut lines: Vec<String> = (0..300).map(|i| format!("{{\"n\": {}}}", i)).collect();
+        lines[100] = "{\"n\": }".to_string();
+        lines[200] = "   ".to_string();
+        let content = lines.join("\n") + "\n";
+        for threads in [1, 4].iter() {
+            let parsed = parse_json_lines_parallel_with(&content, *threads);
+            assert_eq!(299, parsed.len());
+            for (range, result) in parsed.iter() {
+                assert_eq!(parse_json(&content[range.clone()]), *result);
+            }
+            assert!(parsed[100].1.is_err());
+            assert_eq!(Ok(299.0), parsed[298].1.as_ref().unwrap().get_number("n"));
+        }
+    }
+
+    #[test]
+    fn callers_share_the_pool() {
+        let big = big_array(300);
+        let expected = parse_json(&big);
+        std::thread::scope(|scope| {
+            for threads in 2..6 {
+                let (big, expected) = (&big, &expected);
+                scope.spawn(move || for _ in 0..10 {
+                    assert_eq!(*expected, parse_json_parallel_with(big, threads));
+                });
+            }
+        });
+    }
+}
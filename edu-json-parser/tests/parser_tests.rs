@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use edu_json_parser::{parse_json, JsonParser, KeyInterner};
+
+    struct CountingAllocator;
+
+    // Per thread, as other tests allocate on their own threads at the same time
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            // the thread local may be already gone while a thread exits
+            let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn allocations<T, F: FnOnce() -> T>(f: F) -> (T, usize) {
+        let before = ALLOCATIONS.with(Cell::get);
+        let result = f();
+        (result, ALLOCATIONS.with(Cell::get) - before)
+    }
+
+    const CARDS: &str = include!("../benches/many.json");
+
+    #[test]
+    fn same_results_as_parse_json() {
+        let mut parser = JsonParser::new();
+        let sources = [
+            include!("twitter.json"), CARDS, "[1,]", include!("pass01.json"), "{\"a\": \"\\u0041\\n\"}", "", "null"
+        ];
+        for source in sources.iter() {
+            assert_eq!(parse_json(source).ok(), parser.parse(source).ok());
+        }
+    }
+
+    #[test]
+    fn allocates_only_the_output_in_steady_state() {
+        let cards = parse_json(CARDS).unwrap();
+        // every string in the cards fits into `SmolStr` inline, so building the tree costs
+        // exactly as many allocations as cloning it
+        let (_, for_output) = allocations(|| cards.clone());
+
+        let mut parser = JsonParser::new();
+        for _ in 0..3 {
+            parser.parse(CARDS).unwrap();
+        }
+        let (parsed, spent) = allocations(|| parser.parse(CARDS).unwrap());
+        assert_eq!(cards, parsed);
+        assert_eq!(for_output, spent);
+    }
+
+    #[test]
+    fn arrays_are_not_made_of_stacks() {
+        // only fails on toolchains where `split_off(0)` hands out the whole vector
+        let mut parser = JsonParser::new();
+        parser.parse(CARDS).unwrap();
+        let parsed = parser.parse("[1, 2, [3]]").unwrap();
+        assert_eq!(3, parsed.as_array().unwrap().capacity());
+        assert_eq!(1, parsed[2].as_array().unwrap().capacity());
+    }
+
+    #[test]
+    fn keeps_interner_between_calls() {
+        let mut parser = JsonParser::with_interner(KeyInterner::new());
+        parser.parse(r#"{"a_rather_long_key_name_for_sure": 1}"#).unwrap();
+        parser.parse(r#"[{"a_rather_long_key_name_for_sure": 2, "b": 3}]"#).unwrap();
+        assert_eq!(2, parser.interner().unwrap().len());
+        assert!(parser.parse("{\"c\": }").is_err());
+        assert_eq!(Ok(1.0), parser.parse(r#"{"x": 1}"#).unwrap().get_number("x"));
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use edu_json_parser::{
+        parse_json, parse_json_simd, parse_json_lazy, parse_json_tolerant, validate_json, Node, Tape
+    };
+    use edu_json_parser::testing::{arb_node, arb_deep_node, arb_mutated_json};
+
+    fn same_bits(l: &Node, r: &Node) -> bool {
+        match (l, r) {
+            (Node::Number(l), Node::Number(r)) => l.to_bits() == r.to_bits(),
+            (Node::Array(l), Node::Array(r)) => {
+                l.len() == r.len() && l.iter().zip(r).all(|(l, r)| same_bits(l, r))
+            },
+            (Node::Object(l), Node::Object(r)) => {
+                l.len() == r.len() && l.iter().all(|(k, l)| r.get(k).is_some_and(|r| same_bits(l, r)))
+            },
+            _ => l == r
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn serialized_trees_parse_back(node in arb_node()) {
+            let json = node.to_json();
+            let parsed = parse_json(&json).unwrap();
+            prop_assert!(same_bits(&node, &parsed), "{:?} came back as {:?}", node, parsed);
+            prop_assert_eq!(json, parsed.to_json());
+        }
+
+        #[test]
+        fn engines_agree_on_trees(node in arb_deep_node(48)) {
+            let json = node.to_json();
+            prop_assert_eq!(Ok(node.clone()), parse_json_simd(&json));
+            prop_assert_eq!(Ok(node), Tape::parse(&json).map(|tape| tape.to_node()));
+            prop_assert!(validate_json(json.as_bytes()).is_ok());
+        }
+
+        #[test]
+        fn mutated_input_does_not_panic(bytes in arb_mutated_json()) {
+            let _ = validate_json(&bytes);
+            let text = String::from_utf8_lossy(&bytes);
+            let _ = parse_json_tolerant(&text);
+            if let Ok(lazy) = parse_json_lazy(&text) {
+                let _ = lazy.to_node();
+            }
+            prop_assert_eq!(parse_json(&text).ok(), parse_json_simd(&text).ok());
+        }
+    }
+}
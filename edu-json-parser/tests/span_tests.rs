@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, parse_json_spanned, ErrorCause, Position, Span};
+
+    const CARD: &str = "{\n  \"name\": \"Santa\",\n  \"age\": \"ninety nine\",\n  \"sizes\": [120, 120,\n            120],\n  \"имя\": {\"a/b\": null}\n}";
+
+    fn slice(content: &str, span: Span) -> &str {
+        &content[span.start.offset..span.end.offset]
+    }
+
+    #[test]
+    fn every_value_has_a_span() {
+        let (node, spans) = parse_json_spanned(CARD).unwrap();
+        assert_eq!(parse_json(CARD).unwrap(), node);
+        assert_eq!(9, spans.len());
+
+        assert_eq!(CARD, slice(CARD, spans.value_span("").unwrap()));
+        assert_eq!(None, spans.key_span(""));
+        assert_eq!("\"Santa\"", slice(CARD, spans.value_span("/name").unwrap()));
+        assert_eq!("\"name\"", slice(CARD, spans.key_span("/name").unwrap()));
+        assert_eq!("[120, 120,\n            120]", slice(CARD, spans.value_span("/sizes").unwrap()));
+        assert_eq!("null", slice(CARD, spans.value_span("/имя/a~1b").unwrap()));
+
+        let third = spans.value_span("/sizes/2").unwrap();
+        assert_eq!(Position { offset: 79, line: 5, column: 13 }, third.start);
+        assert_eq!(Position { offset: 82, line: 5, column: 16 }, third.end);
+        let key = spans.key_span("/имя").unwrap();
+        assert_eq!(6, key.start.line);
+        assert_eq!(3, key.start.column);
+        // columns are counted in chars, not in bytes
+        assert_eq!(8, key.end.column);
+        assert_eq!(18, spans.value_span("/имя/a~1b").unwrap().start.column);
+    }
+
+    #[test]
+    fn shadowed_duplicates_are_not_recorded() {
+        let content = r#"{"a": {"x": 1}, "a": [true]}"#;
+        let (_, spans) = parse_json_spanned(content).unwrap();
+        assert!(spans.get("/a/x").is_none());
+        assert_eq!("[true]", slice(content, spans.value_span("/a").unwrap()));
+        assert_eq!("true", slice(content, spans.value_span("/a/0").unwrap()));
+    }
+
+    #[test]
+    fn getter_errors_carry_locations() {
+        let (node, spans) = parse_json_spanned(CARD).unwrap();
+        let root = spans.view(&node);
+        assert_eq!(Ok("Santa"), root.get_string("name"));
+
+        let err = root.get_number("age").unwrap_err();
+        assert_eq!(ErrorCause::WrongTypeRequested("age".to_string(), "number", "string"), err.cause);
+        assert_eq!("/age", err.pointer);
+        assert_eq!(3, err.span.as_ref().unwrap().start.line);
+        assert!(err.to_string().contains("line 3, column 10"));
+
+        let sizes = root.get("sizes").unwrap();
+        assert_eq!("/sizes", sizes.pointer());
+        assert_eq!(4, sizes.key_span().unwrap().start.line);
+        let err = sizes.at(3).unwrap_err();
+        assert_eq!(ErrorCause::IndexOutOfBound(3), err.cause);
+        assert_eq!(spans.value_span("/sizes").map(Box::new), err.span);
+        assert_eq!(Some(120.0), sizes.at(2).unwrap().node().as_number());
+        assert_eq!(5, sizes.at(2).unwrap().span().unwrap().start.line);
+
+        let err = root.get("weight").unwrap_err();
+        assert_eq!(ErrorCause::FieldNotExist("weight".to_string()), err.cause);
+        assert_eq!(1, err.span.as_ref().unwrap().start.line);
+
+        let located = spans.locate("/имя", ErrorCause::FieldNotExist("b".to_string()));
+        assert_eq!(6, located.span.as_ref().unwrap().start.line);
+    }
+
+    #[test]
+    fn integer_errors_point_at_the_field() {
+        let content = "{\"n\": 1.5,\n \"big\": 300,\n  \"huge\": 18446744073709551615}";
+        let (node, spans) = parse_json_spanned(content).unwrap();
+        let located = spans.locate("", node.get_u8("n").unwrap_err());
+        assert!(matches!(located.cause, ErrorCause::NotAnInteger(..)));
+        assert_eq!(("/n", "1.5"), (located.pointer.as_str(), slice(content, *located.span.unwrap())));
+        let located = spans.locate("", node.get_u8("big").unwrap_err());
+        assert!(matches!(located.cause, ErrorCause::NumberOutOfRange(..)));
+        assert_eq!(("/big", 2), (located.pointer.as_str(), located.span.unwrap().start.line));
+        let located = spans.locate("", node.get_u64("huge").unwrap_err());
+        assert!(matches!(located.cause, ErrorCause::InexactNumber(..)));
+        assert_eq!(("/huge", 3, 11), (located.pointer.as_str(), located.span.as_ref().unwrap().start.line, located.span.unwrap().start.column));
+    }
+
+    #[test]
+    fn malformed_input_is_an_error() {
+        assert!(parse_json_spanned("{\"a\": }").is_err());
+    }
+}
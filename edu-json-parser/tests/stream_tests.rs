@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json_stream, JsonStream, Framing, Node, RECORD_SEPARATOR};
+
+    #[test]
+    fn concatenated_values() {
+        let content = r#"{"a":1}{"b":2} [3]   "four" 5"#;
+        let stream = parse_json_stream(content);
+        assert_eq!(Framing::Concatenated, stream.framing());
+        let items: Vec<_> = stream.collect();
+        assert_eq!(5, items.len());
+        let ranges: Vec<_> = items.iter().map(|(r, _)| &content[r.clone()]).collect();
+        assert_eq!(vec![r#"{"a":1}"#, r#"{"b":2}"#, "[3]", r#""four""#, "5"], ranges);
+        assert!(items.iter().all(|(_, node)| node.is_ok()));
+        assert_eq!(Ok(1.0), items[0].1.as_ref().unwrap().get_number("a"));
+        assert_eq!(&Ok(Node::Number(5.0)), &items[4].1);
+    }
+
+    #[test]
+    fn concatenated_stops_on_error() {
+        let content = r#"{"a":1} {"b": } {"c":3}"#;
+        let items: Vec<_> = JsonStream::new(content, Framing::Concatenated).collect();
+        assert_eq!(2, items.len());
+        assert!(items[0].1.is_ok());
+        assert!(items[1].1.is_err());
+        assert_eq!(8..content.len(), items[1].0);
+    }
+
+    #[test]
+    fn empty_streams() {
+        assert_eq!(0, parse_json_stream("").count());
+        assert_eq!(0, parse_json_stream("  \n ").count());
+        assert_eq!(0, parse_json_stream("\u{1E}\u{1E}\n").count());
+    }
+
+    #[test]
+    fn record_separated_values() {
+        let content = "\u{1E}{\"a\":1}\n\u{1E}[1, 2]\n\u{1E}\u{1E}42\n";
+        let stream = parse_json_stream(content);
+        assert_eq!(Framing::RecordSeparated, stream.framing());
+        let items: Vec<_> = stream.collect();
+        assert_eq!(3, items.len());
+        assert_eq!("{\"a\":1}", &content[items[0].0.clone()]);
+        assert_eq!("[1, 2]", &content[items[1].0.clone()]);
+        assert_eq!("42", &content[items[2].0.clone()]);
+        assert_eq!(Ok(Node::Number(42.0)), items[2].1);
+    }
+
+    #[test]
+    fn record_separated_recovers_after_truncation() {
+        let content = format!(
+            "{rs}{{\"a\":1}}\n{rs}{{\"b\": [1, 2\n{rs}\"ok\"\n{rs}123{rs}true\n",
+            rs = RECORD_SEPARATOR
+        );
+        let items: Vec<_> = parse_json_stream(&content).collect();
+        assert_eq!(5, items.len());
+        assert!(items[0].1.is_ok());
+        assert!(items[1].1.is_err());
+        assert_eq!("{\"b\": [1, 2\n", &content[items[1].0.clone()]);
+        assert_eq!(Ok(Node::String("ok".into())), items[2].1);
+        // number without a trailing whitespace could be cut in the middle
+        assert!(items[3].1.is_err());
+        assert_eq!("123", &content[items[3].0.clone()]);
+        assert_eq!(Ok(Node::Boolean(true)), items[4].1);
+    }
+
+    #[test]
+    fn record_with_garbage_is_an_error() {
+        let content = "\u{1E}{\"a\":1} x\n\u{1E}null\n";
+        let items: Vec<_> = parse_json_stream(content).collect();
+        assert_eq!(2, items.len());
+        assert!(items[0].1.is_err());
+        assert_eq!(Ok(Node::Null), items[1].1);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{
+        parse_json, parse_json_lenient, parse_json_simd, parse_json_borrowed, parse_json_lazy,
+        parse_json_interned, validate_json, CstDocument, Document, JsonParser, Tape
+    };
+
+    // n_ cases of JSONTestSuite which the old grammar accepted
+    const REJECTED: [&str; 12] = [
+        "[\"a\u{0}a\"]",
+        "[\"\u{1f}\"]",
+        "[\"a\u{7f}\u{8}\"]",
+        "[\"\u{c}\"]",
+        "{\"\u{1}\": 1}",
+        "\u{a0}[1]",
+        "[1\u{2028}]",
+        "[\u{3000}1]",
+        "[\u{b}1]",
+        "[\u{c}1]",
+        "{\"a\":\u{85}1}",
+        "[1]\u{2029}"
+    ];
+
+    fn every_engine(source: &str) -> Vec<bool> {
+        vec![
+            parse_json(source).is_ok(),
+            parse_json_simd(source).is_ok(),
+            parse_json_borrowed(source).is_ok(),
+            parse_json_interned(source).is_ok(),
+            validate_json(source.as_bytes()).is_ok(),
+            Document::parse(source).is_ok(),
+            CstDocument::parse(source).is_ok(),
+            Tape::parse(source).is_ok(),
+            JsonParser::new().parse(source).is_ok(),
+            parse_json_lazy(source).and_then(|node| node.to_node()).is_ok()
+        ]
+    }
+
+    #[test]
+    fn rejects_control_characters_and_unicode_whitespace() {
+        for source in REJECTED.iter() {
+            assert!(every_engine(source).iter().all(|&ok| !ok), "{:?}", source);
+        }
+    }
+
+    #[test]
+    fn accepts_rfc_whitespace() {
+        let sources = [" \t\r\n[ 1 ,\t2\r,\n3 ] \n", "{\r\n\t\"a\" : \"\u{7f}\" }", "\"a\u{a0}b\""];
+        for source in sources.iter() {
+            assert!(every_engine(source).iter().all(|&ok| ok), "{:?}", source);
+        }
+    }
+
+    #[test]
+    fn lenient_keeps_the_old_behavior() {
+        for source in REJECTED.iter() {
+            assert!(parse_json_lenient(source).is_ok(), "{:?}", source);
+        }
+        assert_eq!(parse_json(" [1] ").unwrap(), parse_json_lenient("\u{a0}[1]\u{3000}").unwrap());
+        assert!(parse_json_lenient("[\"a\nb\"]").is_err());
+        assert!(parse_json_lenient("[\"a\tb\"]").is_err());
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, Tape, ErrorCause};
+
+    #[test]
+    fn matches_node_parser() {
+        let sources = [
+            include!("twitter.json"),
+            include!("canada.json"),
+            include!("citm_catalog.json"),
+            include!("pass01.json"),
+            "null", "true", " -1.5e3 ", "\"\\u0041\\n\"", "[]", "{}", "[[], {}, [{}]]"
+        ];
+        for source in sources.iter() {
+            let tape = Tape::parse(source).unwrap();
+            assert_eq!(parse_json(source).unwrap(), tape.to_node());
+        }
+        for source in ["", "[1,]", "{\"a\":}", "{} x"].iter() {
+            assert!(Tape::parse(source).is_err());
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let tape = Tape::parse(r#"{"a": [1, null], "b": true}"#).unwrap();
+        // '{' "a" len '[' 'd' bits 'n' ']' "b" len 't' '}'
+        let tags: Vec<u8> = [0, 1, 3, 4, 6, 7, 8, 10, 11].iter()
+            .map(|&idx| (tape.words()[idx] >> 56) as u8)
+            .collect();
+        assert_eq!(b"{\"[dn]\"t}".to_vec(), tags);
+        assert_eq!(1.0, f64::from_bits(tape.words()[5]));
+        // containers point right after their ends
+        assert_eq!(12, tape.words()[0] & 0xFFFF_FFFF);
+        assert_eq!(8, tape.words()[3] & 0xFFFF_FFFF);
+        assert_eq!(3, tape.words()[7] & 0xFFFF_FFFF);
+    }
+
+    #[test]
+    fn skip_offsets() {
+        let tape = Tape::parse(r#"[{"a": [1, [2, "x"]], "b": {}}, -1.5, "s", null, [[]]]"#).unwrap();
+        let words = tape.words();
+        assert_eq!(29, words.len());
+        // elements are reached by jumping over their siblings
+        let indices: Vec<usize> = tape.root().elements().map(|e| e.index()).collect();
+        assert_eq!(vec![1, 19, 21, 23, 24], indices);
+        for &(open, after, count) in [(0, 29, 5), (1, 19, 2), (4, 14, 2), (7, 13, 2), (16, 18, 0), (24, 28, 1)].iter() {
+            assert_eq!((after, count), (words[open] & 0xFFFF_FFFF, words[open] >> 32 & 0xFF_FFFF), "at {}", open);
+            assert_eq!(open as u64, words[after as usize - 1] & 0xFFFF_FFFF);
+        }
+        assert_eq!(Some(-1.5), tape.root().get_element_at(1).unwrap().as_number());
+        assert_eq!(Ok("x"), tape.root().expect_pointer("/0/a/1").get_element_at(1).map(|x| x.as_string().unwrap()));
+    }
+
+    #[test]
+    fn duplicate_keys_are_shadowed() {
+        let source = r#"{"dup": 1, "a": {"x": [1], "x": 2}, "dup": [3], "b": null, "dup": 4}"#;
+        let tape = Tape::parse(source).unwrap();
+        let root = tape.root();
+        assert_eq!(3, root.len());
+        let keys: Vec<&str> = root.entries().map(|(k, _)| k).collect();
+        assert_eq!(vec!["a", "b", "dup"], keys);
+        assert_eq!(Ok(4.0), root.get_number("dup"));
+        assert_eq!(Ok(2.0), root.get("a").unwrap().get_number("x"));
+        assert_eq!(Err(ErrorCause::FieldNotExist("c".to_string())), root.get("c").map(|_| ()));
+        // shadowed members stay where they were
+        assert_eq!(b'-', (tape.words()[1] >> 56) as u8);
+        assert_eq!(parse_json(source).unwrap(), tape.to_node());
+
+        let same = Tape::parse(r#"{"b": null, "dup": 4.0, "a": {"x": 2}}"#).unwrap();
+        assert!(root == same.root());
+        let other = Tape::parse(r#"{"b": null, "dup": 4, "a": {"x": "2"}}"#).unwrap();
+        assert!(root != other.root());
+        assert!(tape.memory_usage() > 0);
+    }
+}
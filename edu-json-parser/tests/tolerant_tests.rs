@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, parse_json_tolerant, Node};
+
+    fn messages(content: &str) -> (Node, Vec<String>) {
+        let (node, diagnostics) = parse_json_tolerant(content);
+        (node, diagnostics.into_iter().map(|d| d.message).collect())
+    }
+
+    #[test]
+    fn valid_input_gives_no_diagnostics() {
+        let sources = [
+            include!("twitter.json"), include!("canada.json"), include!("pass01.json"),
+            include!("pass02.json"), "null", " \"\\u0041\" ", "{\"a\": 1, \"a\": 2}", "[[], {}]"
+        ];
+        for source in sources.iter() {
+            let (node, diagnostics) = parse_json_tolerant(source);
+            assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+            assert_eq!(parse_json(source).unwrap(), node);
+        }
+    }
+
+    #[test]
+    fn every_failure_of_the_suite_is_reported() {
+        let sources = [
+            include!("fail02.json"), include!("fail03.json"), include!("fail04.json"), include!("fail05.json"),
+            include!("fail06.json"), include!("fail07.json"), include!("fail08.json"), include!("fail09.json"),
+            include!("fail10.json"), include!("fail11.json"), include!("fail12.json"), include!("fail13.json"),
+            include!("fail14.json"), include!("fail15.json"), include!("fail16.json"), include!("fail17.json"),
+            include!("fail19.json"), include!("fail20.json"), include!("fail21.json"), include!("fail22.json"),
+            include!("fail23.json"), include!("fail24.json"), include!("fail25.json"), include!("fail26.json"),
+            include!("fail27.json"), include!("fail28.json"), include!("fail29.json"), include!("fail30.json"),
+            include!("fail31.json"), include!("fail32.json"), include!("fail33.json")
+        ];
+        for source in sources.iter() {
+            if parse_json(source).is_err() {
+                assert!(!parse_json_tolerant(source).1.is_empty(), "{}", source);
+            }
+        }
+    }
+
+    #[test]
+    fn half_typed_documents() {
+        let (node, found) = messages(r#"{"name": "Santa", "age": , "sizes": [1, 2"#);
+        assert_eq!(Ok("Santa"), node.get_string("name"));
+        assert!(node.get("age").unwrap().is_null());
+        assert_eq!(2, node.get("sizes").unwrap().len());
+        assert_eq!(vec!["missing value", "unclosed '['", "unclosed '{'"], found);
+
+        let (node, found) = messages("[1 2, , tru, \"abc\n, {\"a\" 3}]");
+        assert_eq!(6, node.len());
+        assert_eq!(Some(2.0), node[1].as_number());
+        assert!(node[2].is_null() && node[3].is_null());
+        assert_eq!(Some("abc".into()), node[4].as_string());
+        assert_eq!(Ok(3.0), node[5].get_number("a"));
+        assert_eq!(vec!["missing ','", "missing value", "invalid value", "unterminated string", "missing ':'"], found);
+
+        let (node, found) = messages("{\"a\": [1, 2}, \"b\": 3]");
+        assert_eq!(2, node.get("a").unwrap().len());
+        assert_eq!(vec!["unclosed '['", "unexpected characters after the value"], found);
+
+        let (node, found) = messages("{\"a\": 1]}");
+        assert_eq!(Ok(1.0), node.get_number("a"));
+        assert_eq!(vec!["unexpected token"], found);
+
+        let (node, found) = messages("");
+        assert_eq!(Node::Null, node);
+        assert_eq!(vec!["missing value"], found);
+    }
+
+    #[test]
+    fn spans_point_at_the_problem() {
+        let (_, diagnostics) = parse_json_tolerant("{\n  \"a\": tru\n}");
+        assert_eq!(1, diagnostics.len());
+        let span = diagnostics[0].span;
+        assert_eq!((2, 8), (span.start.line, span.start.column));
+        assert_eq!((2, 11), (span.end.line, span.end.column));
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, parse_json_simd, parse_json_simd_with, parse_json_simd_bytes, StructuralIndex, Kernel};
+
+    const KERNELS: [Kernel; 3] = [Kernel::Portable, Kernel::Sse2, Kernel::Avx2];
+
+    fn all_files() -> Vec<&'static str> {
+        vec![
+            include!("canada.json"),
+            include!("citm_catalog.json"),
+            include!("twitter.json"),
+            include!("pass01.json"),
+            include!("pass02.json"),
+            include!("pass03.json"),
+            include!("fail01.json"),
+            include!("fail02.json"),
+            include!("fail03.json"),
+            include!("fail04.json"),
+            include!("fail05.json"),
+            include!("fail06.json"),
+            include!("fail07.json"),
+            include!("fail08.json"),
+            include!("fail09.json"),
+            include!("fail10.json"),
+            include!("fail11.json"),
+            include!("fail12.json"),
+            include!("fail13.json"),
+            include!("fail14.json"),
+            include!("fail15.json"),
+            include!("fail16.json"),
+            include!("fail17.json"),
+            include!("fail18.json"),
+            include!("fail19.json"),
+            include!("fail20.json"),
+            include!("fail21.json"),
+            include!("fail22.json"),
+            include!("fail23.json"),
+            include!("fail24.json"),
+            include!("fail25.json"),
+            include!("fail26.json"),
+            include!("fail27.json"),
+            include!("fail28.json"),
+            include!("fail29.json"),
+            include!("fail30.json"),
+            include!("fail31.json"),
+            include!("fail32.json"),
+            include!("fail33.json"),
+            include!("../benches/many.json"),
+            include!("../benches/spoiled.json"),
+            include!("../benches/twitter.json"),
+            include!("../benches/canada.json"),
+            include!("../benches/citm_catalog.json")
+        ]
+    }
+
+    fn tricky() -> Vec<String> {
+        let mut sources = Vec::new();
+        // escapes and quotes right at block boundaries
+        for pad in 55..70 {
+            let filler = "x".repeat(pad);
+            sources.push(format!(r#"["{}\\", "{}\"", "{}\\\"]", "[,]"]"#, filler, filler, filler));
+            sources.push(format!(r#"{{"{}": "{{}}[],:", "k": [1, 2.5e-3, true, null]}}"#, filler));
+        }
+        for s in [
+            "", " ", "\"", "[\"a]", "[1 2]", "[1,]", "{\"a\" 1}", "{\"a\":1,}", "[\\\"a\"]",
+            "\"\\u0041\\ud800\\x\"", "\"a\tb\"", "\u{a0}[\u{2028}1\u{a0}]\u{3000}", "nul", "truex",
+            "[\"\\u00e9\\/\\b\\f\\n\\r\\t\"]", "{\"a\": {\"b\": []}, \"a\": 2}", "-0.0", "01"
+        ].iter() {
+            sources.push(s.to_string());
+        }
+        sources
+    }
+
+    #[test]
+    fn same_nodes_as_scalar_path() {
+        let tricky = tricky();
+        let sources = all_files().into_iter().chain(tricky.iter().map(|s| s.as_str()));
+        for source in sources {
+            let expected = parse_json(source).ok();
+            for kernel in KERNELS.iter() {
+                assert_eq!(expected, parse_json_simd_with(source, *kernel).ok(), "{:?} on {:?}", kernel, source);
+            }
+            assert_eq!(expected, parse_json_simd(source).ok());
+            assert_eq!(expected, parse_json_simd_bytes(source.as_bytes()).ok());
+        }
+    }
+
+    #[test]
+    fn kernels_agree() {
+        let tricky = tricky();
+        let sources = all_files().into_iter().chain(tricky.iter().map(|s| s.as_str()));
+        for source in sources {
+            let portable = StructuralIndex::build_with(source.as_bytes(), Kernel::Portable);
+            for kernel in KERNELS.iter() {
+                assert_eq!(portable, StructuralIndex::build_with(source.as_bytes(), *kernel));
+            }
+        }
+        assert!(Kernel::detect().is_available());
+    }
+
+    #[test]
+    fn index_contents() {
+        let index = StructuralIndex::build(br#"{"a\"{": [1, "]"]}"#).unwrap();
+        assert_eq!(&[0, 1, 6, 7, 9, 11, 13, 15, 16, 17], index.positions());
+        assert!(StructuralIndex::build(b"\"abc").is_err());
+        assert!(parse_json_simd_bytes(b"[\"\xff\"]").is_err());
+        assert!(parse_json_simd_bytes(b"[\"\xc3\xa9\"]").is_ok());
+    }
+}
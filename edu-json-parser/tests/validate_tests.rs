@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use edu_json_parser::{parse_json, validate_json, ParseError};
+
+    #[test]
+    fn agrees_with_parse_json() {
+        let sources = [
+            include!("twitter.json"), include!("canada.json"), include!("citm_catalog.json"),
+            include!("pass01.json"), include!("pass02.json"), include!("pass03.json"),
+            include!("fail02.json"), include!("fail03.json"), include!("fail09.json"),
+            include!("fail13.json"), include!("fail25.json"), include!("fail32.json"),
+            "", "[1,]", "{\"a\" 1}", "\"\\x\"", "\"\\u12\"", "[\"\\u00e9\"]", " null ", "[1] [2]"
+        ];
+        for source in sources.iter() {
+            assert_eq!(parse_json(source).is_ok(), validate_json(source.as_bytes()).is_ok(), "{:?}", source);
+        }
+    }
+
+    #[test]
+    fn reports_where_it_stopped() {
+        assert_eq!(
+            Err(ParseError { offset: 6, message: "invalid value".to_string() }),
+            validate_json(b"[1, 2, tru]")
+        );
+        assert_eq!(Err(11), validate_json(b"{\"a\": [1,2]]").map_err(|e| e.offset));
+        assert_eq!(Err(2), validate_json(b"[\"\xc3\"]").map_err(|e| e.offset));
+        assert_eq!(Err(1), validate_json(b"[\"abc]").map_err(|e| e.offset));
+        assert_eq!(
+            "unterminated string at byte 1",
+            validate_json(b"[\"abc]").unwrap_err().to_string()
+        );
+    }
+}